@@ -0,0 +1,14 @@
+use clap::{Parser, Subcommand};
+
+/// Arguments for the cache cli command
+#[derive(Parser, Debug)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommands {
+    #[command(about = "Delete every result cached by batched checks")]
+    Clear,
+}
@@ -1,74 +1,39 @@
 use serde::{Deserialize, Serialize};
 use clap::Parser;
 use std::path::PathBuf;
-use llun_core::api_client::AvailableScanner;
-use llun_core::formatters::OutputFormat;
+use llun_core::CheckConfig;
 
 /// Arguments for the check cli command
+/// the check itself is configured by the shared CheckConfig, the rest only makes sense on the command line
 /// NOTE: skip_serialisation_if must be set to allow toml values to
-/// not be overwritten by emty values
-#[derive(Parser, Debug, Serialize, Deserialize)]
+/// not be overwritten by emty values (flags included, or 'false' always wins)
+#[derive(Parser, Debug, Clone, Serialize, Deserialize)]
 pub struct CheckArgs {
-    /// paths from root to desired directory or specific file
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub path: Vec<PathBuf>,
+    #[command(flatten)]
+    #[serde(flatten)]
+    pub check: CheckConfig,
 
-    /// paths otherwise targetted by 'path' that should be skipped from scanning
-    #[arg(short, long)]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub exclude: Vec<PathBuf>,
-
-    /// rules to utilise in the scan (overrides default values)
-    #[arg(short, long)]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub select: Vec<String>,
-
-    /// rules to add to the default to utilise in the scan
+    /// name to report content read from stdin under, also used to pick its config and ignores
     #[arg(long)]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub extend_select: Vec<String>,
-
-    /// rules to ignore from the default list
-    #[arg(short, long)]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub ignore: Vec<String>,
-
-    /// openai model to use under the hood
-    #[arg(short = 'M', long)]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub model: Option<String>,
-
-    /// default ignore all files in the gitignore, to avoid leaking secrets etc...
-    #[arg(short, long, action = clap::ArgAction::SetTrue)]
-    #[serde(default)]
-    pub no_respect_gitignore: bool,
+    pub stdin_filename: Option<PathBuf>,
 
-    /// type of output to give
-    #[arg(short, long)]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub output_format: Vec<OutputFormat>,
-
-    /// llm provider
+    /// read further paths from a newline or NUL separated list, or '-' to read the list from stdin
     #[arg(long)]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub provider: Option<AvailableScanner>,
-
-    /// user provided context (i.e. commit message) to help llun understand the point
-    #[arg(short, long)]
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub context: Option<String>,
+    pub files_from: Option<PathBuf>,
 
-    /// utilise USC to improve the reliability of the model response
+    /// build the prompts and estimate their size and cost without contacting the provider
     #[arg(long, action = clap::ArgAction::SetTrue)]
-    #[serde(default)]
-    pub production_mode: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
 
-    /// files to ignore certain rule violations on i.e. 'main.py::RULE01'
-    #[arg(long)]
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub per_file_ignores: Vec<String>,
+    /// keep running, rescanning files as they change and re-rendering the summary
+    #[arg(short, long, action = clap::ArgAction::SetTrue)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub watch: bool,
 
     /// verbosity of the command, stacks with more 'v's
     #[arg(short = 'v', action = clap::ArgAction::Count)]
     pub verbose: u8,
-}
\ No newline at end of file
+}
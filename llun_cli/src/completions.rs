@@ -0,0 +1,178 @@
+use clap::{Arg, Command, CommandFactory, Parser};
+use clap_complete::env::Shells;
+use clap_complete::{ArgValueCandidates, ArgValueCompleter, CompleteEnv, CompletionCandidate, Shell};
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use llun_core::rules::{RuleManager, RuleManagerError, Severity};
+
+use crate::Cli;
+
+/// Arguments for the completions cli command
+#[derive(Parser, Debug, Clone)]
+pub struct CompletionsArgs {
+    /// shell to generate the completion script for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+/// Arguments for the man cli command
+#[derive(Parser, Debug, Clone)]
+pub struct ManArgs {
+    /// write a page per subcommand into this directory, rather than the main page to stdout
+    #[arg(short, long)]
+    pub out_dir: Option<PathBuf>,
+}
+
+/// the arguments, by subcommand, that take a rule code or rule family
+const RULE_ARGS: &[(&str, &[&str])] = &[
+    ("check", &["select", "extend_select", "ignore"]),
+    ("context", &["select", "extend_select", "ignore"]),
+    ("explain", &["rule_code"]),
+];
+
+/// the arguments, by subcommand, parsed from strings rather than clap enums
+/// so clap cant offer their values itself
+const VALUE_ARGS: &[(&str, &str, &[&str])] = &[
+    ("check", "fail_on", Severity::VALUES),
+    ("report", "min_severity", Severity::VALUES),
+    ("report", "fail_on", Severity::VALUES),
+];
+
+/// the variable the shell sets when it asks llun for completions
+const COMPLETE_VAR: &str = "COMPLETE";
+
+/// answer the shell if it is asking for completions, exiting once done, otherwise carry on as normal
+pub fn complete_if_asked() {
+    CompleteEnv::with_factory(command_with_completers)
+        .var(COMPLETE_VAR)
+        .complete();
+}
+
+/// write the script that hooks a shell up to llun for completions
+/// the script calls back into llun as you type, so rule codes come from whichever project you're in
+pub fn print_completions(shell: Shell) -> Result<(), std::io::Error> {
+    let shell_name = shell.to_string();
+    let shells = Shells::builtins();
+    let Some(completer) = shells.completer(&shell_name) else {
+        return Err(std::io::Error::other(format!(
+            "Completions arent supported for {}",
+            shell_name
+        )));
+    };
+
+    let name = Cli::command().get_name().to_string();
+    completer.write_registration(COMPLETE_VAR, &name, &name, &name, &mut std::io::stdout())
+}
+
+/// write the man page(s) for the cli, built from the same command definition as the completions
+pub fn write_man_pages(args: &ManArgs) -> Result<(), std::io::Error> {
+    let command = Cli::command();
+
+    match &args.out_dir {
+        Some(out_dir) => {
+            std::fs::create_dir_all(out_dir)?;
+            clap_mangen::generate_to(command, out_dir)
+        }
+        None => clap_mangen::Man::new(command).render(&mut std::io::stdout()),
+    }
+}
+
+/// the cli definition with rule codes, families and enum values offered as completions
+/// these args are free text as far as parsing goes, so this is only ever used to complete
+fn command_with_completers() -> Command {
+    let mut command = Cli::command();
+
+    for (subcommand, arg_ids) in RULE_ARGS {
+        for arg_id in *arg_ids {
+            command = command.mut_subcommand(subcommand, |subcommand| {
+                subcommand.mut_arg(arg_id, |arg: Arg| {
+                    arg.add(ArgValueCompleter::new(complete_rule_codes))
+                })
+            });
+        }
+    }
+
+    for (subcommand, arg_id, values) in VALUE_ARGS {
+        command = command.mut_subcommand(subcommand, |subcommand| {
+            subcommand.mut_arg(arg_id, |arg: Arg| {
+                arg.add(ArgValueCandidates::new(|| {
+                    values.iter().map(CompletionCandidate::new).collect::<Vec<_>>()
+                }))
+            })
+        });
+    }
+
+    command
+}
+
+/// every rule code starting with what's been typed, described by its name, followed by the
+/// families they group into. user-defined rules come from the 'llun' folder of the current project
+fn complete_rule_codes(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    // theres nothing sensible to print mid-completion, so a broken rule just means no suggestions
+    let candidates = std::env::current_dir()
+        .ok()
+        .and_then(|current_dir| rule_candidates(&current_dir).ok())
+        .unwrap_or_default();
+
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.get_value().to_string_lossy().starts_with(current.as_ref()))
+        .collect()
+}
+
+fn rule_candidates(root: &Path) -> Result<Vec<CompletionCandidate>, RuleManagerError> {
+    let rule_manager = RuleManager::in_dir(root)?;
+    let mut rule_codes: Vec<String> = rule_manager.valid_rules().iter().cloned().collect();
+    rule_codes.sort();
+
+    let rules = rule_manager.load_from_cli(rule_codes.clone(), Vec::new(), Vec::new())?;
+    let mut families: BTreeMap<String, usize> = BTreeMap::new();
+    for rule_code in &rule_codes {
+        let family = rule_code.trim_end_matches(|c: char| c.is_ascii_digit());
+        *families.entry(family.to_string()).or_default() += 1;
+    }
+
+    let rule_candidates = rules.iter().map(|rule| {
+        CompletionCandidate::new(&rule.rule_code).help(Some(rule.name.clone().into()))
+    });
+    let family_candidates = families.into_iter().map(|(family, count)| {
+        let help = format!("all {} {} rules", count, family);
+        CompletionCandidate::new(family).help(Some(help.into()))
+    });
+
+    Ok(rule_candidates.chain(family_candidates).collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)] // figment::Jail closures have to return a bare figment::Error
+mod tests {
+    use super::*;
+    use figment::Jail;
+
+    fn completions(current: &str) -> Vec<String> {
+        complete_rule_codes(OsStr::new(current))
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rule_codes_are_completed_from_the_current_project() {
+        Jail::expect_with(|jail| {
+            assert!(completions("MINE").is_empty());
+
+            jail.create_dir("llun")?;
+            jail.create_file(
+                "llun/MINE01.json",
+                r#"{"name":"No globals","description":"d","risk_if_violated":"r","examples":[{"violation":"v","better":"b"}]}"#,
+            )?;
+
+            assert_eq!(completions("MINE"), vec!["MINE01", "MINE"]);
+            assert!(completions("SOLID").contains(&"SOLID01".to_string()));
+            Ok(())
+        });
+    }
+}
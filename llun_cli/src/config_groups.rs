@@ -0,0 +1,271 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use tracing::debug;
+
+use llun_core::files::File;
+
+use crate::check_args::CheckArgs;
+use crate::config_loader::{CONFIG_FILENAME, load_config};
+
+/// marks the top of a project, for files outside the one llun was run from
+const GIT_DIR: &str = ".git";
+
+/// a collection of files that share the same effective configuration
+#[derive(Debug)]
+pub struct ConfigGroup {
+    pub config: CheckArgs,
+    pub config_files: Vec<PathBuf>,
+    pub files: Vec<PathBuf>,
+    /// content read from stdin, listed in 'files' under its reporting name
+    pub stdin: Option<File>,
+}
+
+/// split the selected files up by the llun.toml files that sit between them and the root
+/// so that each package in a monorepo gets checked against its own rules
+pub fn group_files_by_config(
+    cli_args: &CheckArgs,
+    files: Vec<PathBuf>,
+) -> Result<Vec<ConfigGroup>, Box<figment::Error>> {
+    let root = std::env::current_dir().unwrap_or_default();
+    let mut found_configs: HashMap<PathBuf, bool> = HashMap::new();
+    let mut files_by_chain: BTreeMap<Vec<PathBuf>, Vec<PathBuf>> = BTreeMap::new();
+
+    for file in files {
+        let chain = nested_config_chain(&file, &root, &mut found_configs);
+        files_by_chain.entry(chain).or_default().push(file);
+    }
+
+    files_by_chain
+        .into_iter()
+        .map(|(chain, files)| {
+            debug!("Config chain {:?} applies to {} files", chain, files.len());
+            Ok(ConfigGroup {
+                config: load_config(cli_args, &chain)?,
+                config_files: chain,
+                files,
+                stdin: None,
+            })
+        })
+        .collect()
+}
+
+/// hand the stdin content to whichever group its reporting name landed in
+pub fn attach_stdin(config_groups: &mut [ConfigGroup], stdin: File) {
+    let stdin_path = PathBuf::from(&stdin.name);
+    if let Some(group) = config_groups
+        .iter_mut()
+        .find(|group| group.files.contains(&stdin_path))
+    {
+        group.stdin = Some(stdin);
+    }
+}
+
+/// every nested llun.toml between the root and the file, outermost first
+/// the root llun.toml is always loaded so is left out of the chain. a file outside the root
+/// only picks up the configs of its own git repo, never whatever happens to sit above it on disk
+fn nested_config_chain(
+    file: &Path,
+    root: &Path,
+    found_configs: &mut HashMap<PathBuf, bool>,
+) -> Vec<PathBuf> {
+    let outside_root = !is_under(file, root);
+    let mut chain = Vec::new();
+
+    for directory in file.ancestors().skip(1) {
+        if is_root(directory, root) {
+            break;
+        }
+
+        let config_path = directory.join(CONFIG_FILENAME);
+        let exists = *found_configs
+            .entry(config_path.clone())
+            .or_insert_with(|| config_path.is_file());
+
+        if exists {
+            chain.push(config_path);
+        }
+
+        if outside_root && directory.join(GIT_DIR).exists() {
+            chain.reverse();
+            return chain;
+        }
+    }
+
+    if outside_root {
+        debug!("{:?} is outside {:?} and any git repo, so has no nested configs", file, root);
+        return Vec::new();
+    }
+
+    chain.reverse();
+    chain
+}
+
+/// relative paths bottom out at '.' or '', absolute ones at the working directory
+fn is_root(directory: &Path, root: &Path) -> bool {
+    directory.as_os_str().is_empty() || directory == Path::new(".") || directory == root
+}
+
+/// relative paths that dont climb out with '..', or absolute ones below the working directory
+fn is_under(file: &Path, root: &Path) -> bool {
+    if file.is_absolute() {
+        return file.starts_with(root);
+    }
+
+    !file
+        .components()
+        .any(|component| component == Component::ParentDir)
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)] // figment::Jail closures have to return a bare figment::Error
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use figment::Jail;
+    use llun_core::rules::Severity;
+
+    fn group(files: &[&str]) -> Result<Vec<ConfigGroup>, figment::Error> {
+        let cli_args = CheckArgs::parse_from(["check", "."]);
+        let files = files.iter().map(PathBuf::from).collect();
+        group_files_by_config(&cli_args, files).map_err(|e| *e)
+    }
+
+    fn chain(file: &str, root: &Path) -> Vec<PathBuf> {
+        nested_config_chain(Path::new(file), root, &mut HashMap::new())
+    }
+
+    #[test]
+    fn files_without_nested_configs_share_a_group() {
+        Jail::expect_with(|jail| {
+            jail.create_file("llun.toml", "model = \"from-root\"")?;
+            jail.create_dir("src")?;
+
+            let groups = group(&["a.py", "src/b.py"])?;
+
+            assert_eq!(groups.len(), 1);
+            assert!(groups[0].config_files.is_empty());
+            assert_eq!(groups[0].files, vec![PathBuf::from("a.py"), PathBuf::from("src/b.py")]);
+            assert_eq!(groups[0].config.check.model.as_deref(), Some("from-root"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn files_are_grouped_by_their_nested_configs() {
+        Jail::expect_with(|jail| {
+            jail.create_file("llun.toml", "select = [\"SOLID\"]\n[severity]\nSOLID01 = \"info\"")?;
+            jail.create_dir("billing")?;
+            jail.create_file(
+                "billing/llun.toml",
+                "select = [\"DOMAIN\"]\n[severity]\nDOMAIN01 = \"error\"",
+            )?;
+            jail.create_dir("reporting")?;
+
+            let groups = group(&["a.py", "billing/b.py", "billing/c.py", "reporting/d.py"])?;
+
+            assert_eq!(groups.len(), 2);
+            let root = groups.iter().find(|group| group.config_files.is_empty()).unwrap();
+            assert_eq!(root.files, vec![PathBuf::from("a.py"), PathBuf::from("reporting/d.py")]);
+            assert_eq!(root.config.check.select, vec!["SOLID"]);
+
+            let billing = groups.iter().find(|group| !group.config_files.is_empty()).unwrap();
+            assert_eq!(billing.config_files, vec![PathBuf::from("billing/llun.toml")]);
+            assert_eq!(
+                billing.files,
+                vec![PathBuf::from("billing/b.py"), PathBuf::from("billing/c.py")]
+            );
+            assert_eq!(billing.config.check.select, vec!["DOMAIN"]);
+            assert_eq!(billing.config.check.severity.get("SOLID01"), Some(&Severity::Info));
+            assert_eq!(billing.config.check.severity.get("DOMAIN01"), Some(&Severity::Error));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn chain_lists_nested_configs_outermost_first() {
+        Jail::expect_with(|jail| {
+            jail.create_file("llun.toml", "")?;
+            jail.create_dir("services/billing/api")?;
+            jail.create_file("services/llun.toml", "")?;
+            jail.create_file("services/billing/api/llun.toml", "")?;
+
+            assert_eq!(
+                chain("services/billing/api/main.py", jail.directory()),
+                vec![
+                    PathBuf::from("services/llun.toml"),
+                    PathBuf::from("services/billing/api/llun.toml"),
+                ]
+            );
+            assert_eq!(
+                chain("./services/billing/main.py", jail.directory()),
+                vec![PathBuf::from("./services/llun.toml")]
+            );
+            assert!(chain("main.py", jail.directory()).is_empty());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn chain_stops_at_the_root_for_absolute_paths() {
+        Jail::expect_with(|jail| {
+            let root = jail.directory().to_path_buf();
+            jail.create_file("llun.toml", "")?;
+            jail.create_dir("services")?;
+            jail.create_file("services/llun.toml", "")?;
+
+            let file = root.join("services/main.py");
+            assert_eq!(
+                chain(file.to_str().unwrap(), &root),
+                vec![root.join("services/llun.toml")]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn chain_outside_the_root_stops_at_its_own_repo() {
+        Jail::expect_with(|jail| {
+            let directory = jail.directory().to_path_buf();
+            let root = directory.join("project");
+            jail.create_dir("project")?;
+            jail.create_file("llun.toml", "")?;
+            jail.create_dir("elsewhere/pkg")?;
+            jail.create_file("elsewhere/llun.toml", "")?;
+            jail.create_file("elsewhere/pkg/llun.toml", "")?;
+
+            let file = directory.join("elsewhere/pkg/main.py");
+            assert!(chain(file.to_str().unwrap(), &root).is_empty());
+
+            jail.create_dir("elsewhere/.git")?;
+            assert_eq!(
+                chain(file.to_str().unwrap(), &root),
+                vec![
+                    directory.join("elsewhere/llun.toml"),
+                    directory.join("elsewhere/pkg/llun.toml"),
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn chain_for_relative_paths_outside_the_root_stops_at_their_repo() {
+        Jail::expect_with(|jail| {
+            jail.create_file("llun.toml", "")?;
+            jail.create_dir("project")?;
+            jail.create_dir("elsewhere/pkg")?;
+            jail.create_file("elsewhere/pkg/llun.toml", "")?;
+            std::env::set_current_dir(jail.directory().join("project")).map_err(|e| e.to_string())?;
+            let root = std::env::current_dir().map_err(|e| e.to_string())?;
+
+            assert!(chain("../elsewhere/pkg/main.py", &root).is_empty());
+
+            std::fs::create_dir(jail.directory().join("elsewhere/.git")).map_err(|e| e.to_string())?;
+            assert_eq!(
+                chain("../elsewhere/pkg/main.py", &root),
+                vec![PathBuf::from("../elsewhere/pkg/llun.toml")]
+            );
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,165 @@
+use figment::{
+    Figment,
+    providers::{Format, Serialized, Toml},
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use llun_core::data::DEFAULT_CONFIG;
+use llun_core::env_provider::LlunEnv;
+
+/// the root level config file, also the name searched for in subdirectories
+pub const CONFIG_FILENAME: &str = "llun.toml";
+
+/// build the users config from each layer in order of precedence:
+/// defaults < pyproject.toml < llun.toml < nested llun.tomls < LLUN_* env vars < cli args
+/// nested configs should be ordered outermost first so the closest wins
+pub fn load_config<T>(cli_args: &T, nested_configs: &[PathBuf]) -> Result<T, Box<figment::Error>>
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let mut figment = Figment::new()
+        .merge(Toml::string(DEFAULT_CONFIG)) // default values are set in the data file
+        .merge(Figment::from(Toml::file("pyproject.toml")).focus("tool.llun"))
+        .merge(Toml::file(CONFIG_FILENAME));
+
+    for nested_config in nested_configs {
+        figment = figment.merge(Toml::file(nested_config));
+    }
+
+    figment
+        .merge(LlunEnv::new())
+        .merge(Serialized::defaults(cli_args))
+        .extract()
+        .map_err(Box::new)
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)] // figment::Jail closures have to return a bare figment::Error
+mod tests {
+    use super::*;
+    use crate::check_args::CheckArgs;
+    use clap::Parser;
+    use figment::Jail;
+    use llun_core::rules::Severity;
+
+    fn load(args: &[&str], nested_configs: &[PathBuf]) -> Result<CheckArgs, figment::Error> {
+        let cli_args = CheckArgs::parse_from(["check", "."].iter().chain(args));
+        load_config(&cli_args, nested_configs).map_err(|e| *e)
+    }
+
+    #[test]
+    fn defaults_apply_without_any_config() {
+        Jail::expect_with(|_jail| {
+            let config = load(&[], &[])?;
+
+            assert_eq!(config.check.model.as_deref(), Some("gpt-4o"));
+            assert_eq!(config.check.select, vec!["SOLID"]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn pyproject_overrides_defaults() {
+        Jail::expect_with(|jail| {
+            jail.create_file("pyproject.toml", "[tool.llun]\nmodel = \"from-pyproject\"")?;
+
+            let config = load(&[], &[])?;
+
+            assert_eq!(config.check.model.as_deref(), Some("from-pyproject"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn llun_toml_overrides_pyproject() {
+        Jail::expect_with(|jail| {
+            jail.create_file("pyproject.toml", "[tool.llun]\nmodel = \"from-pyproject\"")?;
+            jail.create_file("llun.toml", "model = \"from-llun-toml\"")?;
+
+            let config = load(&[], &[])?;
+
+            assert_eq!(config.check.model.as_deref(), Some("from-llun-toml"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn nested_config_overrides_llun_toml() {
+        Jail::expect_with(|jail| {
+            jail.create_file("llun.toml", "model = \"from-llun-toml\"")?;
+            jail.create_dir("service")?;
+            jail.create_file("service/llun.toml", "model = \"from-nested\"")?;
+
+            let config = load(&[], &[PathBuf::from("service/llun.toml")])?;
+
+            assert_eq!(config.check.model.as_deref(), Some("from-nested"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn env_overrides_config_files() {
+        Jail::expect_with(|jail| {
+            jail.create_dir("service")?;
+            jail.create_file("service/llun.toml", "model = \"from-nested\"")?;
+            jail.set_env("LLUN_MODEL", "from-env");
+
+            let config = load(&[], &[PathBuf::from("service/llun.toml")])?;
+
+            assert_eq!(config.check.model.as_deref(), Some("from-env"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn cli_overrides_env() {
+        Jail::expect_with(|jail| {
+            jail.set_env("LLUN_MODEL", "from-env");
+
+            let config = load(&["--model", "from-cli"], &[])?;
+
+            assert_eq!(config.check.model.as_deref(), Some("from-cli"));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn env_lists_and_flags_are_parsed() {
+        Jail::expect_with(|jail| {
+            jail.set_env("LLUN_SELECT", "SOLID, CLASSIC");
+            jail.set_env("LLUN_EXTEND_SELECT", "[\"DOMAIN01\"]");
+            jail.set_env(
+                "LLUN_PER_FILE_IGNORES",
+                "main.py:SOLID01,SOLID02;lib.py:CLASSIC01",
+            );
+            jail.set_env("LLUN_PRODUCTION_MODE", "true");
+
+            let config = load(&[], &[])?;
+
+            assert_eq!(config.check.select, vec!["SOLID", "CLASSIC"]);
+            assert_eq!(config.check.extend_select, vec!["DOMAIN01"]);
+            assert_eq!(
+                config.check.per_file_ignores,
+                vec!["main.py:SOLID01,SOLID02", "lib.py:CLASSIC01"]
+            );
+            assert!(config.check.production_mode);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn nested_severities_merge_with_the_root() {
+        Jail::expect_with(|jail| {
+            jail.create_file("llun.toml", "[severity]\nSOLID01 = \"error\"\nSOLID02 = \"info\"")?;
+            jail.create_dir("service")?;
+            jail.create_file("service/llun.toml", "[severity]\nSOLID02 = \"warning\"")?;
+
+            let config = load(&[], &[PathBuf::from("service/llun.toml")])?;
+
+            assert_eq!(config.check.severity.get("SOLID01"), Some(&Severity::Error));
+            assert_eq!(config.check.severity.get("SOLID02"), Some(&Severity::Warning));
+            Ok(())
+        });
+    }
+}
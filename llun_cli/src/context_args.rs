@@ -1,6 +1,22 @@
 use serde::{Deserialize, Serialize};
 use clap::Parser;
 
+#[derive(Debug, thiserror::Error)]
+pub enum ContextError {
+    #[error("No agent format is configured")]
+    MissingAgentFormat,
+}
+
+impl ContextError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            ContextError::MissingAgentFormat => Some(
+                "pass --agent-format ('agents' or 'copilot-instructions'), or set 'agent_format' in llun.toml"
+                    .to_string(),
+            ),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
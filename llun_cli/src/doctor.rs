@@ -0,0 +1,390 @@
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use llun_core::ErrorReport;
+use llun_core::api_client::{ScannerManager, ScannerSettings};
+use llun_core::api_client::scanner_manager::provider_value_parser;
+use llun_core::env_provider::ENV_PREFIX;
+use llun_core::files::FileManager;
+use llun_core::rules::RuleManager;
+
+use crate::config_loader::{CONFIG_FILENAME, load_config};
+
+/// Arguments for the doctor cli command
+#[derive(Parser, Debug, Clone, Serialize, Deserialize)]
+pub struct DoctorArgs {
+    /// also send a tiny request to the selected provider to check it is reachable
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub connect: bool,
+
+    /// rules to utilise in the scan (overrides default values)
+    #[arg(short, long)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub select: Vec<String>,
+
+    /// rules to add to the default to utilise in the scan
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extend_select: Vec<String>,
+
+    /// rules to ignore from the default list
+    #[arg(short, long)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+
+    /// openai model to use under the hood
+    #[arg(short = 'M', long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// llm provider
+    #[arg(long, value_parser = provider_value_parser())]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// settings for each provider by id, only settable in the tomls
+    #[arg(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scanners: HashMap<String, ScannerSettings>,
+
+    /// default ignore all files in the gitignore, to avoid leaking secrets etc...
+    #[arg(short, long, action = clap::ArgAction::SetTrue)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_respect_gitignore: bool,
+
+    /// verbosity of the command, stacks with more 'v's
+    #[arg(short = 'v', action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// the outcome of a single check, with what to do about it if it didnt pass
+#[derive(Debug)]
+struct Diagnosis {
+    status: Status,
+    summary: String,
+    hint: Option<String>,
+}
+
+/// everything 'llun doctor' found, grouped by what it was looking at
+#[derive(Debug, Default)]
+pub struct DoctorReport {
+    sections: Vec<(String, Vec<Diagnosis>)>,
+}
+
+impl DoctorReport {
+    fn section(&mut self, title: &str) {
+        self.sections.push((title.to_string(), Vec::new()));
+    }
+
+    fn record(&mut self, status: Status, summary: impl Into<String>, hint: Option<String>) {
+        if let Some((_, diagnoses)) = self.sections.last_mut() {
+            diagnoses.push(Diagnosis {
+                status,
+                summary: summary.into(),
+                hint,
+            });
+        }
+    }
+
+    fn pass(&mut self, summary: impl Into<String>) {
+        self.record(Status::Pass, summary, None);
+    }
+
+    fn warn(&mut self, summary: impl Into<String>, hint: impl Into<String>) {
+        self.record(Status::Warn, summary, Some(hint.into()));
+    }
+
+    fn fail(&mut self, summary: impl Into<String>, hint: impl Into<String>) {
+        self.record(Status::Fail, summary, Some(hint.into()));
+    }
+
+    /// whether anything was found that would stop 'llun check' working
+    pub fn has_failures(&self) -> bool {
+        self.count(Status::Fail) > 0
+    }
+
+    fn count(&self, status: Status) -> usize {
+        self.sections
+            .iter()
+            .flat_map(|(_, diagnoses)| diagnoses)
+            .filter(|diagnosis| diagnosis.status == status)
+            .count()
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\x1b[1m============ Llun Doctor ============\x1b[0m")?;
+
+        for (title, diagnoses) in &self.sections {
+            writeln!(f, "\n\x1b[1m{}\x1b[0m", title)?;
+            for diagnosis in diagnoses {
+                let marker = match diagnosis.status {
+                    Status::Pass => "\x1b[32m✓\x1b[0m",
+                    Status::Warn => "\x1b[33m!\x1b[0m",
+                    Status::Fail => "\x1b[31m✗\x1b[0m",
+                };
+                writeln!(f, "  {} {}", marker, diagnosis.summary)?;
+                if let Some(hint) = &diagnosis.hint {
+                    writeln!(f, "      \x1b[2m→ {}\x1b[0m", hint)?;
+                }
+            }
+        }
+
+        writeln!(
+            f,
+            "\n\x1b[1m{} passed, {} warnings, {} failures\x1b[0m",
+            self.count(Status::Pass),
+            self.count(Status::Warn),
+            self.count(Status::Fail)
+        )
+    }
+}
+
+/// look over the environment, config, rules and provider for anything that would trip up a check
+pub async fn run_doctor(cli_args: &DoctorArgs) -> DoctorReport {
+    let mut report = DoctorReport::default();
+
+    report.section("Configuration");
+    let config = match load_config(cli_args, &[]) {
+        Ok(config) => {
+            check_config_files(&mut report);
+            config
+        }
+        Err(e) => {
+            report.fail(
+                format!("Configuration failed to load: {}", e),
+                "fix the value named above in llun.toml, pyproject.toml or the LLUN_* environment variables",
+            );
+            cli_args.clone()
+        }
+    };
+
+    report.section("Providers");
+    check_providers(&mut report, &config);
+
+    report.section("Rules");
+    check_rules(&mut report, &config);
+
+    report.section("Files");
+    check_gitignore(&mut report, &config);
+
+    if config.connect {
+        report.section("Connectivity");
+        check_connection(&mut report, &config).await;
+    }
+
+    report
+}
+
+/// which of the config layers are actually contributing anything
+fn check_config_files(report: &mut DoctorReport) {
+    if Path::new(CONFIG_FILENAME).is_file() {
+        report.pass(format!("Found {}", CONFIG_FILENAME));
+    } else {
+        report.pass(format!("No {} found, using defaults", CONFIG_FILENAME));
+    }
+
+    let pyproject = std::fs::read_to_string("pyproject.toml").unwrap_or_default();
+    if pyproject.contains("[tool.llun") {
+        report.pass("Found [tool.llun] in pyproject.toml");
+    }
+
+    let nested_configs = find_nested_configs();
+    if !nested_configs.is_empty() {
+        let paths: Vec<String> = nested_configs
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        report.pass(format!("Found nested configuration: {}", paths.join(", ")));
+    }
+
+    let env_vars: Vec<String> = std::env::vars()
+        .map(|(key, _)| key)
+        .filter(|key| key.starts_with(ENV_PREFIX) && key != "LLUN_SKIP")
+        .collect();
+    if !env_vars.is_empty() {
+        report.pass(format!("Using environment variables: {}", env_vars.join(", ")));
+    }
+}
+
+/// every llun.toml below the working directory, besides the root one
+fn find_nested_configs() -> Vec<PathBuf> {
+    let files = FileManager::collect_from_cli(vec![PathBuf::from(".")], Vec::new(), false)
+        .unwrap_or_default();
+
+    let mut nested_configs: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|path| path.file_name().is_some_and(|name| name == CONFIG_FILENAME))
+        .filter(|path| {
+            path.parent()
+                .is_some_and(|parent| !parent.as_os_str().is_empty() && parent != Path::new("."))
+        })
+        .collect();
+    nested_configs.sort();
+    nested_configs
+}
+
+/// whether the selected provider and model are usable, and what the other providers are missing
+fn check_providers(report: &mut DoctorReport, config: &DoctorArgs) {
+    let scanner_manager = ScannerManager::new();
+    let selected_id = config.provider.as_deref().map(ScannerManager::canonical_id);
+
+    for factory in scanner_manager.factories() {
+        let missing = factory.missing_env_vars();
+        let selected = selected_id == Some(factory.id());
+        let label = if selected {
+            format!("{} (selected)", factory.id())
+        } else {
+            factory.id().to_string()
+        };
+
+        if missing.is_empty() {
+            report.pass(format!("{} is configured", label));
+        } else {
+            let summary = format!("{} is missing {}", label, missing.join(", "));
+            let hint = format!("export {} {}", missing.join(", "), factory.setup_hint());
+            if selected {
+                report.fail(summary, hint);
+            } else {
+                report.warn(summary, hint);
+            }
+        }
+    }
+
+    let known_ids = scanner_manager.ids().collect::<Vec<_>>().join(" or ");
+    match selected_id {
+        None => report.fail(
+            "No provider is selected",
+            format!("set 'provider' in llun.toml or pass --provider ({})", known_ids),
+        ),
+        Some(id) if !scanner_manager.ids().any(|known| known == id) => report.fail(
+            format!("Provider '{}' doesnt exist", id),
+            format!("set 'provider' to {}", known_ids),
+        ),
+        Some(_) => {}
+    }
+
+    match config.model.as_deref() {
+        Some(model) if !model.trim().is_empty() => {
+            report.pass(format!("Model is set to '{}'", model));
+        }
+        _ => report.fail(
+            "No model is selected",
+            "set 'model' in llun.toml, LLUN_MODEL or pass --model, i.e. 'gpt-4o'",
+        ),
+    }
+}
+
+/// whether the built-in and user-defined rules load, and the selection resolves against them
+fn check_rules(report: &mut DoctorReport, config: &DoctorArgs) {
+    let valid_rules = match RuleManager::get_valid_rules() {
+        Ok(valid_rules) => valid_rules,
+        Err(e) => {
+            report.fail(
+                e.to_string(),
+                "rename the user-defined rule in the 'llun' folder so it doesnt share a built-in rule code",
+            );
+            return;
+        }
+    };
+
+    let user_rules = std::fs::read_dir("llun")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+                .count()
+        })
+        .unwrap_or(0);
+    report.pass(format!(
+        "{} rules available ({} user-defined)",
+        valid_rules.len(),
+        user_rules
+    ));
+
+    let rule_manager = match RuleManager::new() {
+        Ok(rule_manager) => rule_manager,
+        Err(e) => {
+            report.fail(e.to_string(), "check the rule files in the 'llun' folder");
+            return;
+        }
+    };
+    match rule_manager.load_from_cli(
+        config.select.clone(),
+        config.extend_select.clone(),
+        config.ignore.clone(),
+    ) {
+        Ok(rules) if rules.is_empty() => report.warn(
+            "The rule selection is empty, so there is nothing to check against",
+            "loosen 'ignore' or add rules to 'select' / 'extend_select'",
+        ),
+        Ok(rules) => report.pass(format!("{} rules selected", rules.len())),
+        Err(e) => report.fail(
+            format!("The rule selection doesnt resolve: {}", e),
+            "run 'llun explain <RULE>' to check a rule code, or look in the 'llun' folder for custom rules",
+        ),
+    }
+}
+
+/// what protects secrets from being sent to the provider
+fn check_gitignore(report: &mut DoctorReport, config: &DoctorArgs) {
+    if config.no_respect_gitignore {
+        report.warn(
+            "Gitignored files will be scanned, and sent to the provider",
+            "unset 'no_respect_gitignore' unless you are sure nothing ignored is sensitive",
+        );
+    } else if Path::new(".gitignore").is_file() {
+        report.pass("Files matched by .gitignore are skipped");
+    } else {
+        report.warn(
+            "No .gitignore found, so every file under the path is scanned",
+            "add a .gitignore (or use --exclude) to keep secrets and build output away from the provider",
+        );
+    }
+}
+
+/// a real round trip to the selected provider
+async fn check_connection(report: &mut DoctorReport, config: &DoctorArgs) {
+    let (Some(provider), Some(model)) = (config.provider.as_deref(), config.model.as_deref()) else {
+        report.fail(
+            "Skipped, a provider and model are needed to connect",
+            "fix the provider and model issues above first",
+        );
+        return;
+    };
+
+    let scanner_manager = ScannerManager::new().with_settings(config.scanners.clone());
+
+    let started = Instant::now();
+    match scanner_manager.check_connection(model, provider).await {
+        Ok(()) => report.pass(format!(
+            "{} answered using '{}' in {:.1}s",
+            provider,
+            model,
+            started.elapsed().as_secs_f64()
+        )),
+        Err(e) => {
+            // the errors own hint knows more about what went wrong than we do, if it has one
+            let error = ErrorReport::new(&e);
+            report.fail(
+                format!("{} request failed: {}", provider, error),
+                error.hint().unwrap_or_else(|| {
+                    "check the key, endpoint and that the model (or azure deployment) exists and is available to you".to_string()
+                }),
+            )
+        }
+    }
+}
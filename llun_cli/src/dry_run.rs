@@ -0,0 +1,202 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use llun_core::CheckConfig;
+use llun_core::api_client::{PriceTable, PromptManager, ScannerManager, estimate_tokens};
+use llun_core::files::FileSet;
+use llun_core::rules::RuleSet;
+
+/// everything a single config group would send to the provider
+#[derive(Debug)]
+struct GroupEstimate {
+    config_files: Vec<PathBuf>,
+    model: String,
+    rule_codes: Vec<String>,
+    file_tokens: Vec<(String, u64)>,
+    prompt: String,
+    tokens_per_scan: u64,
+    consistency_tokens: u64,
+    production_mode: bool,
+    /// 1 unless 'batch_size' splits the files up
+    batches: usize,
+    batched: bool,
+}
+
+impl GroupEstimate {
+    fn requests(&self) -> usize {
+        self.batches * ScannerManager::request_count(self.production_mode)
+    }
+
+    /// input tokens we know about up front - the consistency pass also receives
+    /// the combined responses, which cant be known without actually scanning
+    /// each batch repeats the system prompt and rules, but the files are split between them
+    fn input_tokens(&self) -> u64 {
+        let batches = self.batches as u64;
+        let file_tokens: u64 = self.file_tokens.iter().map(|(_, tokens)| tokens).sum();
+        let tokens_per_sample = self.tokens_per_scan.saturating_sub(file_tokens) * batches + file_tokens;
+
+        if self.production_mode {
+            let samples = ScannerManager::request_count(true) as u64 - 1;
+            tokens_per_sample * samples + self.consistency_tokens * batches
+        } else {
+            tokens_per_sample
+        }
+    }
+}
+
+/// the result of 'llun check --dry-run': the prompts, what went into them and what they might cost
+#[derive(Debug)]
+pub struct DryRunReport {
+    pricing: PriceTable,
+    groups: Vec<GroupEstimate>,
+}
+
+impl DryRunReport {
+    pub fn new(pricing: PriceTable) -> Self {
+        Self {
+            pricing,
+            groups: Vec::new(),
+        }
+    }
+
+    /// record the prompts built for one group of files
+    /// every file is counted as sent, even if an earlier batched run has cached it
+    pub fn add_group(
+        &mut self,
+        config_files: Vec<PathBuf>,
+        config: &CheckConfig,
+        rules: &RuleSet,
+        files: &FileSet,
+        prompt_manager: &PromptManager,
+    ) {
+        self.groups.push(GroupEstimate {
+            config_files,
+            model: config.model.clone().unwrap_or_default(),
+            rule_codes: rules.iter().map(|rule| rule.rule_code.clone()).collect(),
+            file_tokens: files
+                .iter()
+                .map(|file| (file.name.clone(), estimate_tokens(&file.to_string())))
+                .collect(),
+            prompt: prompt_manager.to_string(),
+            tokens_per_scan: estimate_tokens(&prompt_manager.system_prompt_scan)
+                + estimate_tokens(&prompt_manager.user_prompt),
+            consistency_tokens: estimate_tokens(&prompt_manager.system_prompt_consistency),
+            production_mode: config.production_mode,
+            batches: config
+                .batch_size
+                .map_or(1, |batch_size| files.len().div_ceil(batch_size.get())),
+            batched: config.batch_size.is_some(),
+        });
+    }
+
+    fn write_cost(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        model: &str,
+        input_tokens: u64,
+    ) -> fmt::Result {
+        match self.pricing.estimate_cost(model, input_tokens, 0) {
+            Some(cost) => writeln!(
+                f,
+                "  Estimated cost: ${:.4} ({}, input tokens only)",
+                cost, model
+            ),
+            None => writeln!(
+                f,
+                "  Estimated cost: unknown, no price configured for '{}' (see [pricing] in llun.toml)",
+                model
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, group) in self.groups.iter().enumerate() {
+            writeln!(
+                f,
+                "\x1b[1;34m============ Prompt {} ============\x1b[0m\n",
+                i + 1
+            )?;
+            writeln!(f, "{}\n", group.prompt)?;
+        }
+
+        writeln!(
+            f,
+            "\x1b[1m============ Dry Run Summary ============\x1b[0m\n"
+        )?;
+        writeln!(f, "No requests were sent to the provider.\n")?;
+
+        let mut total_files = 0;
+        let mut total_requests = 0;
+        let mut total_tokens = 0;
+        let mut total_cost = Some(0.0);
+
+        for (i, group) in self.groups.iter().enumerate() {
+            let config_files = if group.config_files.is_empty() {
+                "root configuration".to_string()
+            } else {
+                group
+                    .config_files
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writeln!(f, "\x1b[1mGroup {}\x1b[0m ({})", i + 1, config_files)?;
+            writeln!(f, "  Rules: {}", group.rule_codes.join(", "))?;
+            writeln!(f, "  Files:")?;
+            for (name, tokens) in &group.file_tokens {
+                writeln!(f, "    * {} (~{} tokens)", name, tokens)?;
+            }
+
+            let requests = group.requests();
+            let input_tokens = group.input_tokens();
+            let mode = match (group.production_mode, group.batched) {
+                (true, true) => format!(" (production mode, {} batches)", group.batches),
+                (true, false) => " (production mode)".to_string(),
+                (false, true) => format!(" ({} batches)", group.batches),
+                (false, false) => String::new(),
+            };
+            writeln!(
+                f,
+                "  Requests: {}{}, ~{} tokens per scan, ~{} input tokens in total",
+                requests, mode, group.tokens_per_scan, input_tokens
+            )?;
+            if group.batched {
+                writeln!(f, "  Files cached by an earlier run are counted as if they'll be sent")?;
+            }
+            self.write_cost(f, &group.model, input_tokens)?;
+            writeln!(f)?;
+
+            total_files += group.file_tokens.len();
+            total_requests += requests;
+            total_tokens += input_tokens;
+            total_cost = total_cost
+                .zip(self.pricing.estimate_cost(&group.model, input_tokens, 0))
+                .map(|(a, b)| a + b);
+        }
+
+        writeln!(
+            f,
+            "\x1b[1mTotal:\x1b[0m {} files, {} requests, ~{} input tokens",
+            total_files, total_requests, total_tokens
+        )?;
+        match total_cost {
+            Some(cost) => writeln!(
+                f,
+                "\x1b[1mEstimated cost:\x1b[0m ${:.4} (input tokens only, output depends on the model)",
+                cost
+            )?,
+            None => writeln!(
+                f,
+                "\x1b[1mEstimated cost:\x1b[0m unknown for at least one model"
+            )?,
+        }
+
+        writeln!(
+            f,
+            "\n\x1b[1m=========================================\x1b[0m"
+        )
+    }
+}
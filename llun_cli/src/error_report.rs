@@ -0,0 +1,28 @@
+use std::error::Error;
+use std::fmt::Write;
+
+use llun_core::ErrorReport;
+
+use crate::context_args::ContextError;
+
+/// the error, each of its causes and a hint on fixing it, styled like the doctor's output
+pub fn render_error(error: &(dyn Error + 'static)) -> String {
+    let report = ErrorReport::new(error);
+    let mut rendered = format!("\x1b[1;31merror:\x1b[0m {}", report.error());
+
+    for cause in report.causes() {
+        let _ = write!(rendered, "\n  \x1b[2mcaused by:\x1b[0m {}", cause);
+    }
+    if let Some(hint) = report.hint().or_else(|| cli_hint(error)) {
+        let _ = write!(rendered, "\n  \x1b[2m→ {}\x1b[0m", hint);
+    }
+
+    rendered
+}
+
+/// hints for the errors the cli raises itself, which core cant know about
+fn cli_hint(error: &(dyn Error + 'static)) -> Option<String> {
+    error
+        .downcast_ref::<ContextError>()
+        .and_then(ContextError::hint)
+}
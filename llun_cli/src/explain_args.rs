@@ -0,0 +1,37 @@
+use clap::Parser;
+use llun_core::api_client::ScannerSettings;
+use llun_core::api_client::scanner_manager::provider_value_parser;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Arguments for the explain cli command
+#[derive(Parser, Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainArgs {
+    /// rule code to explain i.e. 'SOLID02' (a rule family explains each rule in it)
+    pub rule_code: String,
+
+    /// file to ask the model to explain the rule against
+    #[arg(short, long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+
+    /// openai model to use under the hood
+    #[arg(short = 'M', long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// llm provider
+    #[arg(long, value_parser = provider_value_parser())]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// settings for each provider by id, only settable in the tomls
+    #[arg(skip)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scanners: HashMap<String, ScannerSettings>,
+
+    /// verbosity of the command, stacks with more 'v's
+    #[arg(short = 'v', action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
@@ -0,0 +1,109 @@
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use llun_core::rules::Severity;
+
+/// marks a hook as ours, so we know its safe to overwrite
+const HOOK_MARKER: &str = "# installed by 'llun hook install'";
+
+/// set this to anything to commit without running llun
+pub const SKIP_VARIABLE: &str = "LLUN_SKIP";
+
+#[derive(Debug, thiserror::Error)]
+pub enum HookError {
+    #[error("Failed to run git, is it installed?")]
+    GitNotFound(#[source] std::io::Error),
+    #[error("Not inside a git repository: {0}")]
+    NotARepository(String),
+    #[error("A pre-commit hook already exists at {0:?}, rerun with --force to replace it")]
+    ExistingHook(PathBuf),
+    #[error("Failed to write the hook")]
+    WriteError(#[from] std::io::Error),
+}
+
+/// Arguments for the hook cli command
+#[derive(Parser, Debug)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    pub command: HookCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HookCommands {
+    #[command(about = "Write a git pre-commit hook that runs llun against staged files")]
+    Install(HookInstallArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct HookInstallArgs {
+    /// lowest severity of issue that blocks the commit (info, warning, error)
+    #[arg(long, default_value = "warning")]
+    pub fail_on: Severity,
+
+    /// replace an existing pre-commit hook that llun didnt write
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub force: bool,
+}
+
+/// write the pre-commit hook into the current repository, returning where it went
+pub fn install_hook(args: &HookInstallArgs) -> Result<PathBuf, HookError> {
+    let hook_path = hooks_dir()?.join("pre-commit");
+
+    if let Ok(existing) = fs::read_to_string(&hook_path)
+        && !existing.contains(HOOK_MARKER)
+        && !args.force
+    {
+        return Err(HookError::ExistingHook(hook_path));
+    }
+
+    if let Some(parent) = hook_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&hook_path, hook_script(args.fail_on))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(hook_path)
+}
+
+/// respects core.hooksPath, unlike assuming '.git/hooks'
+fn hooks_dir() -> Result<PathBuf, HookError> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .map_err(HookError::GitNotFound)?;
+
+    if !output.status.success() {
+        return Err(HookError::NotARepository(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(PathBuf::from(String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+fn hook_script(fail_on: Severity) -> String {
+    format!(
+        r#"#!/bin/sh
+{marker}
+# runs llun against the staged content of this commit
+# bypass with: {skip}=1 git commit ...
+
+if [ -n "${skip}" ]; then
+    echo "llun: {skip} is set, skipping architectural review"
+    exit 0
+fi
+
+exec llun check . --staged --fail-on {fail_on}
+"#,
+        marker = HOOK_MARKER,
+        skip = SKIP_VARIABLE,
+        fail_on = fail_on,
+    )
+}
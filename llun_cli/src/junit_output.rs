@@ -0,0 +1,27 @@
+use std::fs;
+
+use llun_core::api_client::Response;
+use llun_core::formatters::{JunitFormatter, OutputFormatter, OutputFormatterError, OutputManager};
+
+/// where the cli leaves its junit report
+pub const OUTPUT_PATH: &str = "llun-results.xml";
+
+/// junit xml is meant for ci systems to pick up, so the cli writes it to a file rather than the terminal
+pub struct JunitFileFormatter;
+
+impl OutputFormatter for JunitFileFormatter {
+    fn format(&self, response: &Response) -> Result<String, OutputFormatterError> {
+        let xml = JunitFormatter.format(response)?;
+
+        fs::write(OUTPUT_PATH, xml)
+            .map_err(|e| OutputFormatterError::IoError(format!("Failed to write {}: {}", OUTPUT_PATH, e)))?;
+
+        Ok(format!("JUnit report created at: {}", OUTPUT_PATH))
+    }
+}
+
+/// swap the built-in junit formatter for one that writes the report to disk
+pub fn with_junit_file(mut output_manager: OutputManager) -> OutputManager {
+    output_manager.register("junit", Box::new(JunitFileFormatter));
+    output_manager
+}
@@ -1,28 +1,69 @@
 use clap::{Parser, Subcommand};
-use figment::{
-    Figment,
-    providers::{Format, Serialized, Toml},
-};
 use tracing::info;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use llun_core::api_client::{PromptManager, ScannerManager};
-use llun_core::data::DEFAULT_CONFIG;
 use llun_core::files::FileManager;
 use llun_core::formatters::OutputManager;
 use llun_core::rules::RuleManager;
-use llun_core::per_file_ignorer::PerFileIgnorer;
+use llun_core::files::File;
 use llun_core::append_to_file::append_to_file;
+use llun_core::ResultCache;
+use llun_core::result_cache::CACHE_DIR;
+use llun_core::check::CheckError;
 
 pub mod logging;
 use logging::init_tracing;
 
 pub mod context_args;
-use context_args::{ContextArgs, AgentFormat};
+use context_args::{ContextArgs, ContextError, AgentFormat};
 
 pub mod check_args;
 use check_args::CheckArgs;
 
+pub mod config_loader;
+use config_loader::load_config;
+
+pub mod config_groups;
+use config_groups::{attach_stdin, group_files_by_config};
+
+pub mod dry_run;
+use dry_run::DryRunReport;
+
+pub mod scan_runner;
+use scan_runner::{group_llun, scan_groups, select_inputs};
+
+pub mod watcher;
+use watcher::watch;
+
+pub mod explain_args;
+use explain_args::ExplainArgs;
+
+pub mod hook;
+use hook::{HookArgs, HookCommands, install_hook};
+
+pub mod report_args;
+use report_args::{ReportArgs, load_results};
+
+pub mod cache_args;
+use cache_args::{CacheArgs, CacheCommands};
+
+pub mod completions;
+use completions::{CompletionsArgs, ManArgs, complete_if_asked, print_completions, write_man_pages};
+
+pub mod doctor;
+use doctor::{DoctorArgs, run_doctor};
+
+pub mod terminal_markdown;
+use terminal_markdown::render_markdown;
+
+pub mod error_report;
+use error_report::render_error;
+
+pub mod junit_output;
+use junit_output::with_junit_file;
+
 /// CLI for the application
 #[derive(Parser)]
 #[command(name = "llun")]
@@ -35,74 +76,111 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     #[command(about = "Run LLM based architectural survey")]
-    Check(CheckArgs),
+    Check(Box<CheckArgs>),
 
     #[command(about = "Provide architectural context to copilot-instructions.md or AGENTS.md")]
     Context(ContextArgs),
+
+    #[command(about = "Explain a rule, and optionally how it applies to a given file")]
+    Explain(ExplainArgs),
+
+    #[command(about = "Manage the git pre-commit hook")]
+    Hook(HookArgs),
+
+    #[command(about = "Re-render previously saved json results in other output formats")]
+    Report(ReportArgs),
+
+    #[command(about = "Manage the results cached by batched checks")]
+    Cache(CacheArgs),
+
+    #[command(about = "Diagnose problems with the environment, configuration and provider")]
+    Doctor(DoctorArgs),
+
+    #[command(about = "Generate a shell completion script, which completes rule codes from the current project")]
+    Completions(CompletionsArgs),
+
+    #[command(about = "Generate the llun man page(s)")]
+    Man(ManArgs),
 }
 
 
 #[allow(dead_code)] // the codes not dead, just uncalled in the repo
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> ExitCode {
+    complete_if_asked();
     let cli = Cli::parse();
 
+    match run(cli).await {
+        Ok(exit_code) => exit_code,
+        Err(e) => {
+            eprintln!("{}", render_error(e.as_ref()));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// the exit code comes back rather than exiting here, so everything is dropped (and flushed) first
+async fn run(cli: Cli) -> Result<ExitCode, Box<dyn std::error::Error>> {
+
     match cli.command {
         Commands::Check(cli_args) => {
-            let config: CheckArgs = Figment::new()
-                .merge(Toml::string(DEFAULT_CONFIG)) // default values are set in the data file
-                .merge(Toml::file("pyproject.toml").nested())
-                .merge(Toml::file("llun.toml"))
-                .merge(Serialized::defaults(cli_args))
-                .select("tool.llun")
-                .extract()?;
+            let cli_args = *cli_args;
+            let config: CheckArgs = load_config(&cli_args, &[])?;
 
             init_tracing(config.verbose);
             info!("Beginning application...");
 
-            info!("Setting up managers...");
-            let rule_manager = RuleManager::new()?;
-            let scanner_manager = ScannerManager::new()?;
-            let output_manager = OutputManager::new();
-            let per_file_ignorer = PerFileIgnorer::new(config.per_file_ignores)?;
+            let output_manager = with_junit_file(
+                OutputManager::new()
+                    .with_template(config.check.template.as_deref())?
+                    .with_legacy_json(config.check.legacy_json),
+            );
 
             info!("Reading selected files...");
-            let files = FileManager::load_from_cli(
-                config.path,
-                config.exclude,
-                config.no_respect_gitignore,
-            )?;
-            info!("Loading selected rules...");
-            let rules =
-                rule_manager.load_from_cli(config.select, config.extend_select, config.ignore)?;
+            let (file_paths, stdin) = select_inputs(&config)?;
 
-            let prompt_manager = PromptManager::new(&rules, &files, &config.context)?;
+            info!("Resolving nested configuration...");
+            let mut config_groups = group_files_by_config(&cli_args, file_paths)?;
+            if let Some(stdin) = stdin {
+                attach_stdin(&mut config_groups, stdin);
+            }
 
-            info!("Querying selected endpoint...");
-            let model_response = scanner_manager
-                .run_scan(
-                    &prompt_manager.system_prompt_scan,
-                    &prompt_manager.user_prompt,
-                    &config.model.expect("A model must be provided"),
-                    &prompt_manager.system_prompt_consistency,
-                    config.provider.expect("A provider must be provided."),
-                    config.production_mode,
-                )
-                .await?;
+            if config.dry_run {
+                let mut dry_run_report = DryRunReport::new(config.check.pricing.clone());
+                for group in config_groups {
+                    let prepared = group_llun(&group)?.prepare()?;
+                    dry_run_report.add_group(
+                        group.config_files,
+                        &group.config.check,
+                        &prepared.rules,
+                        &prepared.files,
+                        &prepared.prompts,
+                    );
+                }
+                println!("{}", dry_run_report);
+                return Ok(ExitCode::SUCCESS);
+            }
 
-            let filtered_response = per_file_ignorer.apply_ignores(model_response);
+            let report = scan_groups(config_groups).await?;
+
+            if config.watch {
+                info!("Watching for changes...");
+                watch(&cli_args, &config, &output_manager, report.response).await?;
+                return Ok(ExitCode::SUCCESS);
+            }
 
             info!("Processing response...");
-            output_manager.process_response(&filtered_response, &config.output_format)?
+            output_manager.process_report(&report, &config.check.output_format)?;
+
+            if let Some(threshold) = config.check.fail_on
+                && report.fails_on(threshold)
+            {
+                eprintln!("llun: issues found at or above '{}' severity", threshold);
+                return Ok(ExitCode::FAILURE);
+            }
         }
         Commands::Context(cli_args) => {
-            let config: ContextArgs = Figment::new()
-                .merge(Toml::string(DEFAULT_CONFIG))
-                .merge(Toml::file("pyproject.toml").nested())
-                .merge(Toml::file("llun.toml"))
-                .merge(Serialized::defaults(cli_args))
-                .select("tool.llun")
-                .extract()?;
+            let config: ContextArgs = load_config(&cli_args, &[])?;
 
             init_tracing(config.verbose);
             info!("Beginning context creation...");
@@ -115,7 +193,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let prompt = PromptManager::load_system_prompt("system_prompt_agents.txt")?;
             let contextual_prompt = format!("{}\n{}", prompt, rules);
 
-            let format = config.agent_format.expect("A format must be provided.");
+            let format = config.agent_format.ok_or(ContextError::MissingAgentFormat)?;
             let target_path = match format {
                 AgentFormat::CopilotInstructions => PathBuf::from(".github/copilot-instructions.md"),
                 AgentFormat::Agents => PathBuf::from("AGENTS.md"),
@@ -124,6 +202,87 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             info!("Implementing rules context to {:?}...", target_path);
             append_to_file(&target_path, &contextual_prompt)?;
         }
+        Commands::Explain(cli_args) => {
+            let config: ExplainArgs = load_config(&cli_args, &[])?;
+
+            init_tracing(config.verbose);
+            info!("Loading requested rule...");
+            let rule_manager = RuleManager::new()?;
+            let rules = rule_manager.load_from_cli(vec![config.rule_code], Vec::new(), Vec::new())?;
+            println!("{}", render_markdown(&rules.to_string()));
+
+            if let Some(file_path) = config.file {
+                info!("Reading {:?}...", file_path);
+                FileManager::validate_path(&file_path)?;
+                let file = File::from_file(file_path.to_string_lossy().to_string())?;
+
+                let system_prompt = PromptManager::load_system_prompt("system_prompt_explain.txt")?;
+                let user_prompt = PromptManager::load_explain_prompt(&rules, &file)?;
+
+                let model = config.model.ok_or(CheckError::MissingModel)?;
+                let provider = config.provider.ok_or(CheckError::MissingProvider)?;
+
+                info!("Querying selected endpoint...");
+                let scanner_manager = ScannerManager::new().with_settings(config.scanners);
+                let explanation = scanner_manager
+                    .run_explanation(&system_prompt, &user_prompt, &model, &provider)
+                    .await?;
+
+                println!("{}", render_markdown(&explanation));
+            }
+        }
+        Commands::Hook(hook_args) => match hook_args.command {
+            HookCommands::Install(install_args) => {
+                let hook_path = install_hook(&install_args)?;
+                println!("Installed llun pre-commit hook at {:?}", hook_path);
+            }
+        },
+        Commands::Report(cli_args) => {
+            let config: ReportArgs = load_config(&cli_args, &[])?;
+
+            init_tracing(config.verbose);
+            info!("Loading saved results from {:?}...", config.results);
+            let results = load_results(&config)?;
+
+            info!("Processing results...");
+            let output_manager =
+                with_junit_file(OutputManager::new().with_template(config.template.as_deref())?);
+            for output_format in &config.output_format {
+                println!("{}", results.format(&output_manager, output_format)?);
+            }
+
+            if let Some(threshold) = config.fail_on
+                && results.response().max_severity() >= Some(threshold)
+            {
+                eprintln!("llun: issues found at or above '{}' severity", threshold);
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Commands::Cache(cache_args) => match cache_args.command {
+            CacheCommands::Clear => {
+                if ResultCache::clear(Path::new(CACHE_DIR))? {
+                    println!("Cleared the cache at {}", CACHE_DIR);
+                } else {
+                    println!("Nothing to clear, there is no cache at {}", CACHE_DIR);
+                }
+            }
+        },
+        Commands::Doctor(cli_args) => {
+            init_tracing(cli_args.verbose);
+            let report = run_doctor(&cli_args).await;
+            println!("{}", report);
+
+            if report.has_failures() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Commands::Completions(completions_args) => {
+            print_completions(completions_args.shell)?;
+        }
+        Commands::Man(man_args) => {
+            write_man_pages(&man_args)?;
+        }
     }
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
+
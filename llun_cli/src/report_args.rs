@@ -0,0 +1,190 @@
+use clap::Parser;
+use llun_core::Baseline;
+use llun_core::api_client::{Response, ResponseError};
+use llun_core::check::Report;
+use llun_core::formatters::output_manager::output_format_value_parser;
+use llun_core::formatters::{OutputManager, OutputManagerError};
+use llun_core::per_file_ignorer::PerFileIgnorer;
+use llun_core::rules::Severity;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Arguments for the report cli command
+#[derive(Parser, Debug, Clone, Serialize, Deserialize)]
+pub struct ReportArgs {
+    /// results previously saved from 'llun check --output-format json'
+    pub results: PathBuf,
+
+    /// type of output to give
+    #[arg(short, long, value_parser = output_format_value_parser())]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_format: Vec<String>,
+
+    /// minijinja template file rendered by the 'template' output format
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<PathBuf>,
+
+    /// files to ignore certain rule violations on i.e. 'main.py::RULE01'
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_file_ignores: Vec<String>,
+
+    /// earlier saved results whose issues are already known, so only new ones get reported
+    #[arg(short, long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<PathBuf>,
+
+    /// only report issues at least this severe (info, warning, error)
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<Severity>,
+
+    /// exit with a failure if any reported issue is at least this severe (info, warning, error)
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<Severity>,
+
+    /// verbosity of the command, stacks with more 'v's
+    #[arg(short = 'v', action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+/// what 'llun report' was handed: a checks full report, or the bare issues '--legacy-json' (and older llun) saves
+#[derive(Debug)]
+pub enum SavedResults {
+    Report(Report),
+    Response(Response),
+}
+
+impl SavedResults {
+    /// a report if the file holds one, so its metadata makes it through to the output
+    pub fn from_file(path: &Path) -> Result<Self, ResponseError> {
+        match Report::from_file(path) {
+            Ok(report) => Ok(SavedResults::Report(report)),
+            Err(ResponseError::JsonParseError(..)) => Response::from_file(path).map(SavedResults::Response),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn response(&self) -> &Response {
+        match self {
+            SavedResults::Report(report) => &report.response,
+            SavedResults::Response(response) => response,
+        }
+    }
+
+    /// run the issues through a filter, leaving any metadata as it was
+    pub fn map_response(self, filter: impl FnOnce(Response) -> Response) -> Self {
+        match self {
+            SavedResults::Report(mut report) => {
+                report.response = filter(report.response);
+                SavedResults::Report(report)
+            }
+            SavedResults::Response(response) => SavedResults::Response(filter(response)),
+        }
+    }
+
+    /// the results in a single format
+    pub fn format(
+        &self,
+        output_manager: &OutputManager,
+        output_format: &str,
+    ) -> Result<String, OutputManagerError> {
+        match self {
+            SavedResults::Report(report) => output_manager.format_report(report, output_format),
+            SavedResults::Response(response) => output_manager.format_response(response, output_format),
+        }
+    }
+}
+
+/// the saved results with the reports filters applied
+pub fn load_results(config: &ReportArgs) -> Result<SavedResults, Box<dyn std::error::Error>> {
+    let per_file_ignorer = PerFileIgnorer::new(config.per_file_ignores.clone())?;
+    let baseline = config.baseline.as_deref().map(Baseline::from_file).transpose()?;
+
+    let mut results = SavedResults::from_file(&config.results)?
+        .map_response(|response| per_file_ignorer.apply_ignores(response));
+
+    if let Some(baseline) = baseline {
+        results = results.map_response(|response| baseline.apply(response));
+    }
+    if let Some(min_severity) = config.min_severity {
+        results = results.map_response(|response| response.retain_min_severity(min_severity));
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)]
+mod tests {
+    use super::*;
+    use figment::Jail;
+    use llun_core::Llun;
+    use llun_core::api_client::{DetectedIssue, MockScanner};
+
+    fn issue() -> DetectedIssue {
+        DetectedIssue {
+            rule_code: "SOLID01".to_string(),
+            name: "Single Responsibility".to_string(),
+            file_path: "app.py".to_string(),
+            brief_description: "Does too much".to_string(),
+            explanation: "Two reasons to change in one class.".to_string(),
+            suggested_alternative: "Split it up.".to_string(),
+            code_snippet: "class App:".to_string(),
+            severity: Severity::default(),
+        }
+    }
+
+    fn rerender(results: &str) -> Result<String, figment::Error> {
+        let config = ReportArgs::parse_from(["report", results, "-o", "json"]);
+        let results = load_results(&config).map_err(|e| e.to_string())?;
+        Ok(results
+            .format(&OutputManager::new(), "json")
+            .map_err(|e| e.to_string())?)
+    }
+
+    #[test]
+    fn a_saved_report_rerenders_with_its_metadata() {
+        Jail::expect_with(|jail| {
+            jail.create_file("app.py", "class App:\n    pass\n")?;
+            let llun = Llun::builder()
+                .path("app.py")
+                .model("gpt-4o")
+                .scanner(MockScanner::new(Response {
+                    detected_issues: vec![issue()],
+                    usage: None,
+                }))
+                .build()
+                .map_err(|e| e.to_string())?;
+            let report = tokio::runtime::Runtime::new()
+                .map_err(|e| e.to_string())?
+                .block_on(llun.check())
+                .map_err(|e| e.to_string())?;
+            let checked = llun.format(&report, "json").map_err(|e| e.to_string())?;
+            jail.create_file("results.json", &checked)?;
+
+            assert_eq!(rerender("results.json")?, checked);
+            assert!(checked.contains("\"schema_version\""));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn a_legacy_response_rerenders_as_is() {
+        Jail::expect_with(|jail| {
+            let legacy = Response {
+                detected_issues: vec![issue()],
+                usage: None,
+            };
+            let saved = OutputManager::new()
+                .format_response(&legacy, "json")
+                .map_err(|e| e.to_string())?;
+            jail.create_file("results.json", &saved)?;
+
+            assert_eq!(rerender("results.json")?, saved);
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use llun_core::files::{File, FileManager, GitIndex, STDIN_PATH};
+use llun_core::{Llun, LlunCoreError, Report};
+
+use crate::check_args::CheckArgs;
+use crate::config_groups::ConfigGroup;
+
+/// reporting name for stdin content when no '--stdin-filename' is given
+const DEFAULT_STDIN_FILENAME: &str = "stdin";
+
+/// every file path the config selects, plus the content of stdin if '-' was one of them
+/// the stdin file is listed in the paths under its reporting name, so it is grouped like any other
+pub fn select_inputs(
+    config: &CheckArgs,
+) -> Result<(Vec<PathBuf>, Option<File>), Box<dyn std::error::Error>> {
+    let stdin_path = Path::new(STDIN_PATH);
+    let reads_stdin = config.check.path.iter().any(|path| path == stdin_path);
+    let mut paths: Vec<PathBuf> = config
+        .check
+        .path
+        .iter()
+        .filter(|path| *path != stdin_path)
+        .cloned()
+        .collect();
+
+    if reads_stdin && (config.check.staged || config.watch) {
+        return Err("reading from stdin cant be combined with --staged or --watch".into());
+    }
+    // the watcher only follows the paths it was given, so would miss (or overreach) a list or the index
+    if config.watch && (config.check.staged || config.files_from.is_some()) {
+        return Err("--watch cant be combined with --staged or --files-from, pass the paths to watch instead".into());
+    }
+
+    if let Some(files_from) = &config.files_from {
+        if reads_stdin && files_from == stdin_path {
+            return Err("stdin cant be used for both content ('-') and --files-from -".into());
+        }
+        info!("Reading file list from {:?}...", files_from);
+        paths.extend(FileManager::read_path_list(files_from)?);
+    }
+
+    let mut file_paths = FileManager::collect_from_cli(
+        paths,
+        config.check.exclude.clone(),
+        config.check.no_respect_gitignore,
+    )?;
+
+    if config.check.staged {
+        info!("Filtering to staged files...");
+        let index = GitIndex::open()?;
+        file_paths.retain(|path| index.is_staged(path));
+    }
+
+    let stdin = if reads_stdin {
+        info!("Reading content from stdin...");
+        let name = config
+            .stdin_filename
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_STDIN_FILENAME));
+        // the buffer being piped in replaces whatever is saved on disk
+        file_paths.retain(|path| *path != name);
+        file_paths.push(name.clone());
+
+        Some(File::from_reader(
+            name.to_string_lossy().to_string(),
+            std::io::stdin().lock(),
+        )?)
+    } else {
+        None
+    };
+
+    Ok((file_paths, stdin))
+}
+
+/// a check of one group of files against its own config
+/// stdin is handed over as an in-memory file, so its reporting name is never read from disk
+pub fn group_llun(group: &ConfigGroup) -> Result<Llun, LlunCoreError> {
+    let mut config = group.config.check.clone();
+    config.path = group.files.clone();
+
+    let mut builder = Llun::builder().config(config);
+    if let Some(stdin) = &group.stdin {
+        builder = builder.file(stdin.clone());
+    }
+
+    builder.build()
+}
+
+/// check each group of files against its own config, and combine the results
+pub async fn scan_groups(config_groups: Vec<ConfigGroup>) -> Result<Report, LlunCoreError> {
+    let mut combined_report: Option<Report> = None;
+
+    for group in config_groups {
+        let report = group_llun(&group)?.check().await?;
+        match &mut combined_report {
+            Some(combined_report) => combined_report.merge(report),
+            None => combined_report = Some(report),
+        }
+    }
+
+    Ok(combined_report.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn select(args: &[&str]) -> Result<(Vec<PathBuf>, Option<File>), String> {
+        let config = CheckArgs::parse_from(["check"].iter().chain(args));
+        select_inputs(&config).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn watch_only_follows_the_paths_it_was_given() {
+        for args in [&["--watch", "--staged"][..], &["--watch", "--files-from", "changed.txt"]] {
+            let error = select(args).unwrap_err();
+            assert!(error.contains("--watch cant be combined"), "{:?}: {}", args, error);
+        }
+        assert!(select(&["-", "--watch"]).unwrap_err().contains("stdin"));
+    }
+}
@@ -0,0 +1,66 @@
+/// make markdown (from our rules or the model) a little easier on the eye in a terminal
+/// this isnt a full markdown parser, just enough to make headings, code and emphasis stand out
+pub fn render_markdown(markdown: &str) -> String {
+    let mut output = String::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            output.push_str(&format!("    \x1b[90m{}\x1b[0m\n", line));
+        } else if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim();
+            output.push_str(&format!("\x1b[1;34m{}\x1b[0m\n", render_inline(heading)));
+        } else if trimmed == "---" {
+            output.push_str("\x1b[90m────────────────────────────────────────\x1b[0m\n");
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            let indent = &line[..line.len() - trimmed.len()];
+            output.push_str(&format!("{}  • {}\n", indent, render_inline(item)));
+        } else {
+            output.push_str(&render_inline(line));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+/// bold, italic and inline code within a single line
+fn render_inline(line: &str) -> String {
+    let line = replace_pairs(line, "**", "\x1b[1m", "\x1b[22m");
+    let line = replace_pairs(&line, "`", "\x1b[33m", "\x1b[39m");
+    replace_pairs(&line, "*", "\x1b[3m", "\x1b[23m")
+}
+
+/// swap matched pairs of a delimiter for ansi codes, leaving any unmatched delimiter alone
+fn replace_pairs(line: &str, delimiter: &str, open: &str, close: &str) -> String {
+    let parts: Vec<&str> = line.split(delimiter).collect();
+    if parts.len() < 3 {
+        return line.to_string();
+    }
+
+    let mut output = String::new();
+    let pairs = (parts.len() - 1) / 2;
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            if i > pairs * 2 {
+                output.push_str(delimiter);
+            } else if i % 2 == 1 {
+                output.push_str(open);
+            } else {
+                output.push_str(close);
+            }
+        }
+        output.push_str(part);
+    }
+    output
+}
@@ -0,0 +1,198 @@
+use notify_debouncer_mini::{DebounceEventResult, new_debouncer, notify::RecursiveMode};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use llun_core::api_client::{DetectedIssue, Response, Usage};
+use llun_core::files::{FileManager, normalise_path};
+use llun_core::formatters::OutputManager;
+
+use crate::check_args::CheckArgs;
+use crate::config_groups::group_files_by_config;
+use crate::scan_runner::scan_groups;
+
+/// editors tend to write files in a flurry, so wait for things to settle before rescanning
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(750);
+
+/// the latest issues for each file, so unchanged files keep their results between scans
+/// along with what every scan since watching started has cost
+#[derive(Debug, Default)]
+pub struct WatchResults {
+    issues_by_file: BTreeMap<String, Vec<DetectedIssue>>,
+    usage: Option<Usage>,
+}
+
+impl WatchResults {
+    pub fn from_response(response: Response) -> Self {
+        let mut results = Self::default();
+        results.insert(response);
+        results
+    }
+
+    /// swap in fresh results for the rescanned files, dropping whatever they reported before
+    pub fn update(&mut self, rescanned: &[PathBuf], response: Response) {
+        self.remove(rescanned);
+        self.insert(response);
+    }
+
+    /// forget any results for the given files
+    pub fn remove(&mut self, files: &[PathBuf]) {
+        for file in files {
+            self.issues_by_file
+                .remove(normalise_path(&file.to_string_lossy()));
+        }
+    }
+
+    pub fn to_response(&self) -> Response {
+        Response {
+            detected_issues: self.issues_by_file.values().flatten().cloned().collect(),
+            usage: self.usage,
+        }
+    }
+
+    fn insert(&mut self, response: Response) {
+        self.usage = Usage::combine(self.usage, response.usage);
+
+        for issue in response.detected_issues {
+            self.issues_by_file
+                .entry(normalise_path(&issue.file_path).to_string())
+                .or_default()
+                .push(issue);
+        }
+    }
+}
+
+/// keep llun running, rescanning files as they change and re-rendering the summary each time
+pub async fn watch(
+    cli_args: &CheckArgs,
+    config: &CheckArgs,
+    output_manager: &OutputManager,
+    initial_response: Response,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_WINDOW, move |result: DebounceEventResult| {
+        // the receiver only goes away when we stop watching, so theres no one left to tell
+        let _ = sender.send(result);
+    })?;
+
+    for path in &config.check.path {
+        debouncer.watcher().watch(path, RecursiveMode::Recursive)?;
+    }
+
+    let mut results = WatchResults::from_response(initial_response);
+    let mut known_files = select_files(config)?;
+    render(output_manager, &results)?;
+
+    while let Some(event_result) = receiver.recv().await {
+        let events = match event_result {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("File watcher error: {}", e);
+                continue;
+            }
+        };
+        let changed: HashSet<PathBuf> = events
+            .into_iter()
+            .map(|event| canonical(&event.path))
+            .collect();
+
+        let selected_files = select_files(config)?;
+        let modified: Vec<PathBuf> = selected_files
+            .iter()
+            .filter(|(canonical_path, _)| changed.contains(*canonical_path))
+            .map(|(_, path)| path.clone())
+            .collect();
+        let removed: Vec<PathBuf> = known_files
+            .iter()
+            .filter(|(canonical_path, _)| {
+                changed.contains(*canonical_path) && !selected_files.contains_key(*canonical_path)
+            })
+            .map(|(_, path)| path.clone())
+            .collect();
+        known_files = selected_files;
+
+        if modified.is_empty() && removed.is_empty() {
+            continue;
+        }
+
+        info!("Rescanning {} changed file(s)...", modified.len());
+        results.remove(&removed);
+        if !modified.is_empty() {
+            let config_groups = group_files_by_config(cli_args, modified.clone())?;
+            match scan_groups(config_groups).await {
+                Ok(report) => results.update(&modified, report.response),
+                Err(e) => error!("Rescan failed, keeping previous results: {}", e),
+            }
+        }
+        render(output_manager, &results)?;
+    }
+
+    Ok(())
+}
+
+/// the files the config currently selects, keyed by canonical path so they can be matched to events
+fn select_files(
+    config: &CheckArgs,
+) -> Result<HashMap<PathBuf, PathBuf>, Box<dyn std::error::Error>> {
+    let files = FileManager::collect_from_cli(
+        config.check.path.clone(),
+        config.check.exclude.clone(),
+        config.check.no_respect_gitignore,
+    )?;
+
+    Ok(files
+        .into_iter()
+        .map(|path| (canonical(&path), path))
+        .collect())
+}
+
+/// deleted files cant be canonicalised, so fall back to the path as given
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// clear the terminal and redraw the summary
+fn render(
+    output_manager: &OutputManager,
+    results: &WatchResults,
+) -> Result<(), Box<dyn std::error::Error>> {
+    print!("\x1b[2J\x1b[H");
+    output_manager.process_response(&results.to_response(), &["summary".to_string()])?;
+    println!("\x1b[90mWatching for changes... (Ctrl+C to stop)\x1b[0m");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(prompt_tokens: Option<u64>) -> Response {
+        Response {
+            detected_issues: Vec::new(),
+            usage: Some(Usage::request(prompt_tokens, prompt_tokens, Duration::from_millis(100))),
+        }
+    }
+
+    #[test]
+    fn rescans_add_to_the_usage() {
+        let mut results = WatchResults::from_response(response(Some(10)));
+        results.update(&[PathBuf::from("a.py")], response(Some(5)));
+
+        let usage = results.to_response().usage.unwrap();
+        assert_eq!(usage.requests, 2);
+        assert_eq!(usage.prompt_tokens, Some(15));
+        assert_eq!(usage.latency_ms, 200);
+    }
+
+    #[test]
+    fn unreported_tokens_stay_unknown_across_rescans() {
+        let mut results = WatchResults::from_response(response(Some(10)));
+        results.update(&[PathBuf::from("a.py")], response(None));
+
+        let usage = results.to_response().usage.unwrap();
+        assert_eq!(usage.requests, 2);
+        assert_eq!(usage.total_tokens(), None);
+    }
+}
@@ -0,0 +1,23 @@
+"""Run llun in-process: load rules, build a file set, scan it and format what was found."""
+
+from .llun import (
+    DetectedIssue,
+    FileSet,
+    LlunError,
+    Response,
+    RuleSet,
+    Scanner,
+    format_response,
+    load_rules,
+)
+
+__all__ = [
+    "DetectedIssue",
+    "FileSet",
+    "LlunError",
+    "Response",
+    "RuleSet",
+    "Scanner",
+    "format_response",
+    "load_rules",
+]
@@ -0,0 +1,97 @@
+from collections.abc import Awaitable, Sequence
+from os import PathLike
+
+class LlunError(Exception):
+    """Raised whenever llun itself fails."""
+
+class RuleSet:
+    @property
+    def codes(self) -> list[str]: ...
+    def __len__(self) -> int: ...
+
+class FileSet:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def from_paths(
+        paths: Sequence[str | PathLike[str]],
+        exclude: Sequence[str | PathLike[str]] = ...,
+        no_respect_gitignore: bool = False,
+    ) -> FileSet: ...
+    def add(self, name: str, content: str) -> None: ...
+    @property
+    def names(self) -> list[str]: ...
+    def __len__(self) -> int: ...
+
+class DetectedIssue:
+    def __init__(
+        self,
+        rule_code: str,
+        name: str,
+        file_path: str,
+        brief_description: str,
+        explanation: str,
+        suggested_alternative: str,
+        code_snippet: str,
+        severity: str = "warning",
+    ) -> None: ...
+    @property
+    def rule_code(self) -> str: ...
+    @property
+    def name(self) -> str: ...
+    @property
+    def file_path(self) -> str: ...
+    @property
+    def brief_description(self) -> str: ...
+    @property
+    def explanation(self) -> str: ...
+    @property
+    def suggested_alternative(self) -> str: ...
+    @property
+    def code_snippet(self) -> str: ...
+    @property
+    def severity(self) -> str: ...
+
+class Response:
+    def __init__(self, detected_issues: Sequence[DetectedIssue] = ...) -> None: ...
+    @staticmethod
+    def from_json(json: str) -> Response: ...
+    def to_json(self) -> str: ...
+    @property
+    def detected_issues(self) -> list[DetectedIssue]: ...
+    @property
+    def max_severity(self) -> str | None: ...
+    def __len__(self) -> int: ...
+
+class Scanner:
+    def __init__(self, provider: str = "openai") -> None: ...
+    @staticmethod
+    def mock(response: Response = ...) -> Scanner: ...
+    def scan(
+        self,
+        rules: RuleSet,
+        files: FileSet,
+        *,
+        model: str,
+        production_mode: bool = False,
+        context: str | None = None,
+    ) -> Awaitable[Response]: ...
+    def scan_blocking(
+        self,
+        rules: RuleSet,
+        files: FileSet,
+        *,
+        model: str,
+        production_mode: bool = False,
+        context: str | None = None,
+    ) -> Response: ...
+
+def load_rules(
+    select: Sequence[str],
+    extend_select: Sequence[str] = ...,
+    ignore: Sequence[str] = ...,
+) -> RuleSet: ...
+def format_response(
+    response: Response,
+    output_format: str = "summary",
+    template: str | PathLike[str] | None = None,
+) -> str: ...
@@ -0,0 +1,56 @@
+import json
+
+import pytest
+
+from llun import DetectedIssue, FileSet, Response
+
+
+@pytest.fixture(autouse=True)
+def workspace(tmp_path, monkeypatch):
+    """Run every test in an empty project, so user-defined rules from elsewhere don't leak in."""
+    monkeypatch.chdir(tmp_path)
+    return tmp_path
+
+
+@pytest.fixture
+def user_rule(workspace):
+    """A user-defined rule in the project's 'llun' folder."""
+    rules_dir = workspace / "llun"
+    rules_dir.mkdir()
+    (rules_dir / "MINE01.json").write_text(
+        json.dumps(
+            {
+                "name": "No globals",
+                "description": "Module level state should be passed in explicitly.",
+                "risk_if_violated": "Hidden coupling between callers.",
+                "examples": [{"violation": "CACHE = {}", "better": "def run(cache): ..."}],
+                "severity": "error",
+            }
+        )
+    )
+    return "MINE01"
+
+
+@pytest.fixture
+def issue():
+    return DetectedIssue(
+        rule_code="SOLID01",
+        name="Single Responsibility Principle",
+        file_path="app.py",
+        brief_description="Handler parses requests and talks to the database",
+        explanation="Two reasons to change in one class.",
+        suggested_alternative="Move persistence into a repository.",
+        code_snippet="class Handler: ...",
+    )
+
+
+@pytest.fixture
+def response(issue):
+    return Response([issue])
+
+
+@pytest.fixture
+def files():
+    file_set = FileSet()
+    file_set.add("app.py", "class Handler:\n    pass\n")
+    return file_set
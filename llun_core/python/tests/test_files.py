@@ -0,0 +1,26 @@
+import pytest
+
+from llun import FileSet, LlunError
+
+
+def test_files_can_be_added_by_hand():
+    files = FileSet()
+    files.add("unsaved.py", "print('hi')\n")
+
+    assert files.names == ["unsaved.py"]
+    assert len(files) == 1
+
+
+def test_files_are_read_from_paths(workspace):
+    (workspace / "src").mkdir()
+    (workspace / "src" / "app.py").write_text("x = 1\n")
+    (workspace / "src" / "skip.py").write_text("y = 2\n")
+
+    files = FileSet.from_paths(["src"], exclude=["src/skip.py"])
+
+    assert [name.replace("\\", "/") for name in files.names] == ["src/app.py"]
+
+
+def test_missing_path_raises():
+    with pytest.raises(LlunError):
+        FileSet.from_paths(["does_not_exist"])
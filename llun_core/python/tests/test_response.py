@@ -0,0 +1,77 @@
+import json
+
+import pytest
+
+from llun import DetectedIssue, LlunError, Response, format_response
+
+
+def test_issue_fields(issue):
+    assert issue.rule_code == "SOLID01"
+    assert issue.name == "Single Responsibility Principle"
+    assert issue.suggested_alternative == "Move persistence into a repository."
+    assert issue.code_snippet == "class Handler: ..."
+    assert issue.severity == "warning"
+
+
+def test_invalid_severity_raises():
+    with pytest.raises(ValueError, match="Unknown severity"):
+        DetectedIssue("SOLID01", "n", "f", "b", "e", "s", "c", severity="fatal")
+
+
+def test_json_round_trip(response):
+    loaded = Response.from_json(response.to_json())
+
+    assert json.loads(loaded.to_json()) == json.loads(response.to_json())
+    assert loaded.detected_issues[0].explanation == "Two reasons to change in one class."
+
+
+def test_invalid_json_raises():
+    with pytest.raises(LlunError):
+        Response.from_json("{}")
+
+
+@pytest.mark.parametrize("output_format", ["json", "azure", "summary"])
+def test_every_format_mentions_the_issue(response, output_format):
+    assert "SOLID01" in format_response(response, output_format)
+
+
+def test_junit_format_is_the_xml_and_writes_nothing(response, workspace):
+    formatted = format_response(response, "junit")
+
+    assert formatted.startswith("<?xml")
+    assert "SOLID01" in formatted
+    assert not (workspace / "llun-results.xml").exists()
+
+
+def test_json_format_is_the_response(response):
+    formatted = json.loads(format_response(response, "json"))
+
+    assert formatted["detected_issues"][0]["file_path"] == "app.py"
+
+
+def test_unknown_format_raises(response):
+    with pytest.raises(ValueError, match="Unknown output format"):
+        format_response(response, "xml")
+
+
+def test_template_format_renders_the_template(response, workspace):
+    template = workspace / "slack.j2"
+    template.write_text(
+        "{{ detected_issues | length }} issue(s), worst {{ max_severity }}"
+        "{% for issue in detected_issues %}: {{ issue.rule_code }} in {{ issue.file_path }}{% endfor %}"
+    )
+
+    assert format_response(response, "template", template) == "1 issue(s), worst warning: SOLID01 in app.py"
+
+
+def test_template_format_needs_a_template(response):
+    with pytest.raises(LlunError, match="no template given"):
+        format_response(response, "template")
+
+
+def test_broken_template_raises(response, workspace):
+    template = workspace / "broken.j2"
+    template.write_text("{{ unclosed")
+
+    with pytest.raises(LlunError, match="syntax error"):
+        format_response(response, "template", template)
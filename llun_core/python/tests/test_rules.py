@@ -0,0 +1,34 @@
+import pytest
+
+from llun import LlunError, load_rules
+
+
+def test_families_expand_to_their_rules():
+    rules = load_rules(["SOLID"])
+
+    assert sorted(rules.codes) == ["SOLID01", "SOLID02", "SOLID03", "SOLID04", "SOLID05"]
+    assert len(rules) == 5
+
+
+def test_extend_select_and_ignore():
+    rules = load_rules(["SOLID"], extend_select=["DOMAIN01"], ignore=["SOLID02"])
+
+    assert "DOMAIN01" in rules.codes
+    assert "SOLID02" not in rules.codes
+
+
+def test_user_defined_rules_are_loaded(user_rule):
+    rules = load_rules([user_rule])
+
+    assert rules.codes == [user_rule]
+    assert "No globals" in str(rules)
+
+
+def test_unknown_rule_raises():
+    with pytest.raises(LlunError, match="NOTARULE01"):
+        load_rules(["NOTARULE01"])
+
+
+def test_unknown_rule_hints_at_the_families():
+    with pytest.raises(LlunError, match="hint: pick a rule .* from .*SOLID"):
+        load_rules(["NOTARULE01"])
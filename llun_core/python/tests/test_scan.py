@@ -0,0 +1,74 @@
+import asyncio
+
+from llun import Response, Scanner, load_rules
+
+
+def test_scan_is_awaitable(response, files):
+    scanner = Scanner.mock(response)
+
+    async def scan():
+        return await scanner.scan(load_rules(["SOLID"]), files, model="gpt-4o")
+
+    result = asyncio.run(scan())
+
+    assert isinstance(result, Response)
+    assert [issue.rule_code for issue in result.detected_issues] == ["SOLID01"]
+
+
+def test_scans_run_concurrently(response, files):
+    scanner = Scanner.mock(response)
+    rules = load_rules(["SOLID"])
+
+    async def scan_twice():
+        return await asyncio.gather(
+            scanner.scan(rules, files, model="gpt-4o"),
+            scanner.scan(rules, files, model="gpt-4o", production_mode=True),
+        )
+
+    single, production = asyncio.run(scan_twice())
+
+    assert len(single) == 1
+    # production mode merges its samples in a consistency pass, which the mock answers like any other scan
+    assert len(production) == 1
+
+
+def test_scan_blocking(response, files):
+    result = Scanner.mock(response).scan_blocking(
+        load_rules(["SOLID"]), files, model="gpt-4o", context="A small web app."
+    )
+
+    issue = result.detected_issues[0]
+    assert issue.file_path == "app.py"
+    assert issue.brief_description == "Handler parses requests and talks to the database"
+
+
+def test_severities_come_from_the_rules(issue, files, user_rule):
+    response = Response(
+        [
+            issue,
+            type(issue)(
+                rule_code=user_rule,
+                name="No globals",
+                file_path="app.py",
+                brief_description="Module level cache",
+                explanation="",
+                suggested_alternative="",
+                code_snippet="CACHE = {}",
+                severity="info",
+            ),
+        ]
+    )
+
+    result = Scanner.mock(response).scan_blocking(
+        load_rules(["SOLID01", user_rule]), files, model="gpt-4o"
+    )
+
+    assert [issue.severity for issue in result.detected_issues] == ["warning", "error"]
+    assert result.max_severity == "error"
+
+
+def test_empty_mock_finds_nothing(files):
+    result = Scanner.mock().scan_blocking(load_rules(["SOLID"]), files, model="gpt-4o")
+
+    assert len(result) == 0
+    assert result.max_severity is None
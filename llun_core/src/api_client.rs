@@ -1,13 +1,24 @@
-pub mod available_scanner;
+pub mod mock_scanner;
+pub mod model_pricing;
 pub mod openai_scanner;
+pub mod openai_scanner_factory;
 pub mod prompt_manager;
 pub mod response;
 pub mod scanner;
+pub mod scanner_factory;
 pub mod scanner_manager;
+pub mod token_estimate;
+pub mod usage;
 
-pub use available_scanner::AvailableScanner;
+pub use mock_scanner::MockScanner;
+pub use model_pricing::{ModelPrice, PriceTable};
 pub use openai_scanner::{OpenAiClientError, OpenAiScanner};
+pub use openai_scanner_factory::{AzureOpenAiScannerFactory, OpenAiScannerFactory};
 pub use prompt_manager::{PromptManager, PromptManagerError};
-pub use response::{DetectedIssue, Response};
+pub use response::{DetectedIssue, Response, ResponseError};
 pub use scanner::{Scanner, ScannerError};
-pub use scanner_manager::{ScannerManager, ScannerManagerError};
+pub use scanner_factory::{ScannerFactory, ScannerSettings};
+pub(crate) use scanner_factory::SharedScannerFactory;
+pub use scanner_manager::{ScanProgress, ScannerManager, ScannerManagerError};
+pub use token_estimate::estimate_tokens;
+pub use usage::Usage;
@@ -0,0 +1,36 @@
+use crate::api_client::{Response, Scanner, ScannerError};
+
+/// a scanner that never leaves the machine, it answers every scan with the same canned response
+/// handy for testing anything built on llun without an api key (or a bill)
+#[derive(Debug, Clone, Default)]
+pub struct MockScanner {
+    response: Response,
+}
+
+impl MockScanner {
+    pub fn new(response: Response) -> Self {
+        Self { response }
+    }
+}
+
+#[async_trait::async_trait]
+impl Scanner for MockScanner {
+    async fn scan_files(
+        &self,
+        _system_prompt: &str,
+        _user_prompt: &str,
+        _model: &str,
+    ) -> Result<Response, ScannerError> {
+        Ok(self.response.clone())
+    }
+
+    /// the canned response as json, so theres always something to show
+    async fn complete(
+        &self,
+        _system_prompt: &str,
+        _user_prompt: &str,
+        _model: &str,
+    ) -> Result<String, ScannerError> {
+        Ok(serde_json::to_string(&self.response)?)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::api_client::{Response, Scanner, ScannerError};
+use crate::api_client::scanner::{UNPARSABLE_REPLY_HINT, openai_hint};
+use crate::api_client::{Response, Scanner, ScannerError, ScannerSettings, Usage};
 use async_openai::{
     Client,
     types::{
@@ -6,14 +7,16 @@ use async_openai::{
         CreateChatCompletionRequestArgs,
     },
 };
+use std::time::Instant;
+use tracing::{debug, info};
 
 #[derive(Debug, thiserror::Error)]
 pub enum OpenAiClientError {
     #[error("Empty response from model")]
     EmptyResponse,
-    #[error("OpenAI API request failed: {0}")]
+    #[error("OpenAI API request failed")]
     ApiRequestFailed(#[from] async_openai::error::OpenAIError),
-    #[error("Failed to parse response as JSON: {0}")]
+    #[error("Failed to parse response as JSON")]
     JsonParseError(#[from] serde_json::Error),
     #[error("Failed to extract json from response")]
     JsonCleaningError,
@@ -21,6 +24,21 @@ pub enum OpenAiClientError {
     MissingEnvVar(String),
 }
 
+impl OpenAiClientError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            OpenAiClientError::EmptyResponse
+            | OpenAiClientError::JsonParseError(_)
+            | OpenAiClientError::JsonCleaningError => Some(UNPARSABLE_REPLY_HINT.to_string()),
+            OpenAiClientError::ApiRequestFailed(e) => openai_hint(e),
+            OpenAiClientError::MissingEnvVar(env_var) => Some(format!(
+                "export {}, 'llun doctor' lists everything each provider needs",
+                env_var
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum OpenAiScanner {
     Public(Client<async_openai::config::OpenAIConfig>),
@@ -30,13 +48,41 @@ pub enum OpenAiScanner {
 #[async_trait::async_trait]
 impl Scanner for OpenAiScanner {
     /// get the models response to our lovely prompts
-    /// taken from https://github.com/64bit/async-openai/blob/main/examples/chat/src/main.rs
     async fn scan_files(
         &self,
         system_prompt: &str,
         user_prompt: &str,
         model: &str,
     ) -> Result<Response, ScannerError> {
+        let (content, usage) = self.request(system_prompt, user_prompt, model).await?;
+        debug!("Model replied: {}", content);
+        let cleaned_content = Self::extract_json_from_response(&content)?;
+        let mut formatted_response: Response = serde_json::from_str(cleaned_content)?;
+        formatted_response.usage = Some(usage);
+
+        Ok(formatted_response)
+    }
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+    ) -> Result<String, ScannerError> {
+        let (content, _) = self.request(system_prompt, user_prompt, model).await?;
+        Ok(content)
+    }
+}
+
+impl OpenAiScanner {
+    /// taken from https://github.com/64bit/async-openai/blob/main/examples/chat/src/main.rs
+    /// returns the reply along with what it cost, servers that dont report usage leave the tokens unknown
+    async fn request(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+    ) -> Result<(String, Usage), ScannerError> {
         let request = CreateChatCompletionRequestArgs::default()
             .model(model)
             .temperature(0.1)
@@ -52,47 +98,71 @@ impl Scanner for OpenAiScanner {
             ])
             .build()?;
 
+        let started = Instant::now();
         let response = match self {
             OpenAiScanner::Public(client) => client.chat().create(request).await?,
             OpenAiScanner::Azure(client) => client.chat().create(request).await?,
         };
+        let tokens = response.usage.as_ref();
+        let usage = Usage::request(
+            tokens.map(|tokens| tokens.prompt_tokens as u64),
+            tokens.map(|tokens| tokens.completion_tokens as u64),
+            started.elapsed(),
+        );
+        match tokens {
+            Some(tokens) => info!(
+                "Request took {}ms, {} prompt + {} completion tokens",
+                usage.latency_ms, tokens.prompt_tokens, tokens.completion_tokens
+            ),
+            None => info!(
+                "Request took {}ms, the provider didnt report its token usage",
+                usage.latency_ms
+            ),
+        }
+
         let content = response
             .choices
             .first()
-            .and_then(|choice| choice.message.content.as_ref())
-            .ok_or(ScannerError::OpenAiClientError(
-                "Empty response".to_string(),
-            ))?;
-        let cleaned_content =
-            Self::extract_json_from_response(content).map_err(Self::map_openai_client_error)?;
-        let formatted_response: Response = serde_json::from_str(cleaned_content)?;
+            .and_then(|choice| choice.message.content.clone())
+            .ok_or(OpenAiClientError::EmptyResponse)?;
 
-        Ok(formatted_response)
+        Ok((content, usage))
     }
-}
 
-impl OpenAiScanner {
     /// instantiate a public openai instance
     pub fn new() -> Result<Self, OpenAiClientError> {
-        let client = Client::new(); // it auto pulls the key env var
-        Ok(Self::Public(client))
+        Self::new_with(&ScannerSettings::new())
+    }
+
+    /// instantiate a public openai instance, pointed at 'api_base' if its set (i.e. an openai compatible server)
+    /// openai itself needs a key, local servers often dont so its left to them to refuse
+    pub fn new_with(settings: &ScannerSettings) -> Result<Self, OpenAiClientError> {
+        let mut config = async_openai::config::OpenAIConfig::new(); // it auto pulls the key env var
+        match settings.get("api_base").and_then(|value| value.as_str()) {
+            Some(api_base) => config = config.with_api_base(api_base),
+            None if std::env::var_os("OPENAI_API_KEY").is_none() => {
+                return Err(OpenAiClientError::MissingEnvVar("OPENAI_API_KEY".to_string()));
+            }
+            None => {}
+        }
+
+        Ok(Self::Public(Client::with_config(config)))
     }
 
     /// instantiate an azure openai instance
     pub fn new_azure() -> Result<Self, OpenAiClientError> {
+        Self::new_azure_with(&ScannerSettings::new())
+    }
+
+    /// instantiate an azure openai instance, with 'endpoint', 'api_version' and 'deployment'
+    /// taken from the settings over the environment. the key only ever comes from the environment
+    pub fn new_azure_with(settings: &ScannerSettings) -> Result<Self, OpenAiClientError> {
         // the docs dont seem to suggest it can auto pull these sadly :( :( :(
         let api_key = std::env::var("AZURE_OPENAI_API_KEY")
             .map_err(|_| OpenAiClientError::MissingEnvVar("AZURE_OPENAI_API_KEY".to_string()))?;
-
-        let endpoint = std::env::var("AZURE_OPENAI_ENDPOINT")
-            .map_err(|_| OpenAiClientError::MissingEnvVar("AZURE_OPENAI_ENDPOINT".to_string()))?;
-
-        let api_version = std::env::var("AZURE_OPENAI_API_VERSION").map_err(|_| {
-            OpenAiClientError::MissingEnvVar("AZURE_OPENAI_API_VERSION".to_string())
-        })?;
-
-        let deployment = std::env::var("AZURE_OPENAI_DEPLOYMENT")
-            .map_err(|_| OpenAiClientError::MissingEnvVar("AZURE_OPENAI_DEPLOYMENT".to_string()))?;
+        let endpoint = Self::setting_or_env(settings, "endpoint", "AZURE_OPENAI_ENDPOINT")?;
+        let api_version = Self::setting_or_env(settings, "api_version", "AZURE_OPENAI_API_VERSION")?;
+        let deployment = Self::setting_or_env(settings, "deployment", "AZURE_OPENAI_DEPLOYMENT")?;
 
         let config = async_openai::config::AzureConfig::new()
             .with_api_key(api_key)
@@ -125,7 +195,15 @@ impl OpenAiScanner {
         Ok(&content[start_pos..=end_pos])
     }
 
-    fn map_openai_client_error(err: OpenAiClientError) -> ScannerError {
-        ScannerError::OpenAiClientError(format!("{err}"))
+    fn setting_or_env(
+        settings: &ScannerSettings,
+        key: &str,
+        env_var: &str,
+    ) -> Result<String, OpenAiClientError> {
+        match settings.get(key).and_then(|value| value.as_str()) {
+            Some(value) => Ok(value.to_string()),
+            None => std::env::var(env_var)
+                .map_err(|_| OpenAiClientError::MissingEnvVar(env_var.to_string())),
+        }
     }
 }
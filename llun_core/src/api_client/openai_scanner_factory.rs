@@ -0,0 +1,52 @@
+use crate::api_client::{OpenAiScanner, Scanner, ScannerError, ScannerFactory, ScannerSettings};
+
+/// the public openai api, or anything that speaks it if given an 'api_base'
+pub struct OpenAiScannerFactory;
+
+impl ScannerFactory for OpenAiScannerFactory {
+    fn id(&self) -> &str {
+        "openai"
+    }
+
+    fn required_env_vars(&self) -> &[&str] {
+        &["OPENAI_API_KEY"]
+    }
+
+    fn setup_hint(&self) -> &str {
+        "with a key from https://platform.openai.com/api-keys"
+    }
+
+    fn create(&self, settings: &ScannerSettings) -> Result<Box<dyn Scanner>, ScannerError> {
+        let scanner = OpenAiScanner::new_with(settings)?;
+        Ok(Box::new(scanner))
+    }
+}
+
+/// an azure openai deployment
+pub struct AzureOpenAiScannerFactory;
+
+impl ScannerFactory for AzureOpenAiScannerFactory {
+    fn id(&self) -> &str {
+        "azure-openai"
+    }
+
+    /// 'endpoint', 'api_version' and 'deployment' can be set in the settings instead,
+    /// but doctor cant know that so lists them all
+    fn required_env_vars(&self) -> &[&str] {
+        &[
+            "AZURE_OPENAI_API_KEY",
+            "AZURE_OPENAI_ENDPOINT",
+            "AZURE_OPENAI_API_VERSION",
+            "AZURE_OPENAI_DEPLOYMENT",
+        ]
+    }
+
+    fn setup_hint(&self) -> &str {
+        "using the values listed on your foundry instance"
+    }
+
+    fn create(&self, settings: &ScannerSettings) -> Result<Box<dyn Scanner>, ScannerError> {
+        let scanner = OpenAiScanner::new_azure_with(settings)?;
+        Ok(Box::new(scanner))
+    }
+}
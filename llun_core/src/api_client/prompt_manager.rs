@@ -5,8 +5,9 @@ use serde::{Deserialize, Serialize};
 use serde_json;
 
 use crate::api_client::Response;
+use crate::content_hash::content_hash;
 use crate::data::PROMPT_DIR;
-use crate::files::FileSet;
+use crate::files::{File, FileSet};
 use crate::rules::RuleSet;
 
 /// errors that can occur in the prompt manager
@@ -16,7 +17,7 @@ pub enum PromptManagerError {
     FileNotFound(String),
     #[error("File is not valid UTF-8: {0}")]
     InvalidUtf8(String),
-    #[error("JSON parsing failed: {source}")]
+    #[error("JSON parsing failed")]
     JsonError {
         #[from]
         source: serde_json::Error,
@@ -49,6 +50,23 @@ impl PromptManager {
         })
     }
 
+    /// a hash of the scan prompts (and the schema in them), so results from an older prompt arent reused
+    pub fn version() -> Result<String, PromptManagerError> {
+        let prompt_path = "user_prompt_scan.txt";
+        let user_prompt_template = PROMPT_DIR
+            .get_file(prompt_path)
+            .ok_or_else(|| PromptManagerError::FileNotFound(prompt_path.to_string()))?
+            .contents_utf8()
+            .ok_or_else(|| PromptManagerError::InvalidUtf8(prompt_path.to_string()))?;
+
+        Ok(content_hash(format!(
+            "{}\n{}\n{}",
+            Self::load_system_prompt("system_prompt_scan.txt")?,
+            Self::load_system_prompt("system_prompt_consistency.txt")?,
+            user_prompt_template
+        )))
+    }
+
     /// load in and format the system prompt
     pub fn load_system_prompt(prompt_filename: &str) -> Result<String, PromptManagerError> {
         let schema = schema_for!(Response);
@@ -94,6 +112,38 @@ impl PromptManager {
 
         Ok(formatted_prompt)
     }
+
+    /// load in and format the prompt asking for an explanation of some rules in a single file
+    pub fn load_explain_prompt(rules: &RuleSet, file: &File) -> Result<String, PromptManagerError> {
+        let prompt_path = "user_prompt_explain.txt";
+
+        let prompt_template = PROMPT_DIR
+            .get_file(prompt_path)
+            .ok_or_else(|| PromptManagerError::FileNotFound(prompt_path.to_string()))?
+            .contents_utf8()
+            .ok_or_else(|| PromptManagerError::InvalidUtf8(prompt_path.to_string()))?;
+
+        Ok(prompt_template
+            .replace("{rules}", &rules.to_string())
+            .replace("{file}", &file.to_string()))
+    }
+
+    /// load in and format a prompt for an agent to work against the selected rules with
+    pub fn load_agent_prompt(
+        prompt_filename: &str,
+        rules: &RuleSet,
+        task: &str,
+    ) -> Result<String, PromptManagerError> {
+        let prompt_template = PROMPT_DIR
+            .get_file(prompt_filename)
+            .ok_or_else(|| PromptManagerError::FileNotFound(prompt_filename.to_string()))?
+            .contents_utf8()
+            .ok_or_else(|| PromptManagerError::InvalidUtf8(prompt_filename.to_string()))?;
+
+        Ok(prompt_template
+            .replace("{rules}", &rules.to_string())
+            .replace("{task}", task))
+    }
 }
 
 impl fmt::Display for PromptManager {
@@ -1,9 +1,37 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+use crate::api_client::Usage;
+use crate::rules::Severity;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResponseError {
+    #[error("Failed to read saved results from {0}")]
+    IoError(String, #[source] std::io::Error),
+    #[error("Saved results in {0} arent valid llun json")]
+    JsonParseError(String, #[source] serde_json::Error),
+}
+
+impl ResponseError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            ResponseError::IoError(..) => Some("check the path to the saved results".to_string()),
+            ResponseError::JsonParseError(..) => Some(
+                "save results with the json output format, i.e. 'llun check . -o json > results.json'"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct Response {
     pub detected_issues: Vec<DetectedIssue>,
+    /// filled in by the scanner, so hidden from the model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub usage: Option<Usage>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -15,4 +43,35 @@ pub struct DetectedIssue {
     pub explanation: String,
     pub suggested_alternative: String,
     pub code_snippet: String,
+    /// filled in from the rule after scanning, so hidden from the model
+    #[serde(default)]
+    #[schemars(skip)]
+    pub severity: Severity,
+}
+
+impl Response {
+    /// load results previously saved from the json output format
+    pub fn from_file(path: &Path) -> Result<Self, ResponseError> {
+        let name = path.display().to_string();
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| ResponseError::IoError(name.clone(), e))?;
+        serde_json::from_str(&contents).map_err(|e| ResponseError::JsonParseError(name, e))
+    }
+
+    /// add the usage of another request to this ones, unknown usage adds nothing
+    pub fn add_usage(&mut self, usage: Option<Usage>) {
+        self.usage = Usage::combine(self.usage, usage);
+    }
+
+    /// drop any issues less severe than the given level
+    pub fn retain_min_severity(mut self, min_severity: Severity) -> Self {
+        self.detected_issues
+            .retain(|issue| issue.severity >= min_severity);
+        self
+    }
+
+    /// the most severe issue found, if any were
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.detected_issues.iter().map(|issue| issue.severity).max()
+    }
 }
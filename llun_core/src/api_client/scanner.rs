@@ -1,24 +1,102 @@
-use crate::api_client::Response;
+use crate::api_client::{OpenAiClientError, Response};
 use async_openai::error::OpenAIError;
 
+/// what a model says when it doesnt reply with the json we asked for, which happens more than you'd hope
+pub(crate) const UNPARSABLE_REPLY_HINT: &str = "the model didnt reply with the json llun asked for, rerun the check, try a more capable model or use --production-mode (-vv logs the reply)";
+
 #[derive(Debug, thiserror::Error)]
 pub enum ScannerError {
-    #[error("Open AI client raised an error")]
+    #[error("Request to the provider failed")]
     OpenAiError(#[from] OpenAIError),
-    #[error("Failed to json load")]
+    #[error("The models reply isnt valid llun json")]
     FailedLoadingJson(#[from] serde_json::Error),
-    #[error("Failed dealing with OpenAiClient {0}")]
-    OpenAiClientError(String),
+    #[error(transparent)]
+    OpenAiClientError(#[from] OpenAiClientError),
+    /// for scanners outside llun, whatever went wrong for them
+    #[error("Scanner failed")]
+    Other(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl ScannerError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            ScannerError::OpenAiError(e) => openai_hint(e),
+            ScannerError::FailedLoadingJson(_) => Some(UNPARSABLE_REPLY_HINT.to_string()),
+            // transparent, so its never seen on its own when walking the chain
+            ScannerError::OpenAiClientError(e) => e.hint(),
+            ScannerError::Other(_) => None,
+        }
+    }
+}
+
+/// the usual reasons a request to an openai style api fails
+pub(crate) fn openai_hint(error: &OpenAIError) -> Option<String> {
+    match error {
+        OpenAIError::Reqwest(_) => Some(
+            "check the provider is reachable and its endpoint (or 'api_base') is right, 'llun doctor --connect' tests it"
+                .to_string(),
+        ),
+        OpenAIError::ApiError(api_error) => {
+            let code = api_error.code.as_deref().unwrap_or_default();
+            let message = api_error.message.to_lowercase();
+            if code == "invalid_api_key" || message.contains("api key") {
+                Some("check your api key is set and valid, 'llun doctor --connect' tests it".to_string())
+            } else if code == "model_not_found" || code == "DeploymentNotFound" {
+                Some("check the model (or azure deployment) exists and your key can use it".to_string())
+            } else {
+                None
+            }
+        }
+        OpenAIError::JSONDeserialize(_) => Some(
+            "the provider replied with something other than the openai api, check the endpoint (or 'api_base') is right"
+                .to_string(),
+        ),
+        _ => None,
+    }
 }
 
 /// abstract concept of a tool that can scan files
 /// In most cases, id imagine this will be a wrapper on an LLM client
 #[async_trait::async_trait]
-pub trait Scanner {
+pub trait Scanner: Send + Sync {
     async fn scan_files(
         &self,
         system_prompt: &str,
         user_prompt: &str,
         model: &str,
     ) -> Result<Response, ScannerError>;
+
+    /// get the models free text reply, for when we want prose rather than a list of issues
+    /// optional, so scanners written before it existed keep compiling, they just cant explain
+    async fn complete(
+        &self,
+        _system_prompt: &str,
+        _user_prompt: &str,
+        _model: &str,
+    ) -> Result<String, ScannerError> {
+        Err(ScannerError::Other("not supported by this scanner".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a scanner from before 'complete' existed, that only knows how to scan
+    struct ScanOnly;
+
+    #[async_trait::async_trait]
+    impl Scanner for ScanOnly {
+        async fn scan_files(&self, _: &str, _: &str, _: &str) -> Result<Response, ScannerError> {
+            Ok(Response::default())
+        }
+    }
+
+    #[test]
+    fn complete_is_unsupported_unless_implemented() {
+        let error = futures::executor::block_on(ScanOnly.complete("system", "user", "model")).unwrap_err();
+
+        assert!(matches!(error, ScannerError::Other(_)));
+        assert_eq!(std::error::Error::source(&error).unwrap().to_string(), "not supported by this scanner");
+    }
 }
@@ -0,0 +1,72 @@
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+use crate::api_client::{Response, Scanner, ScannerError};
+
+/// settings for one scanner, from its '[scanners.<id>]' table in the tomls (empty if there isnt one)
+pub type ScannerSettings = Map<String, Value>;
+
+/// anything that can build a scanner, registered with the ScannerManager under its id
+/// secrets should stay in the environment, the settings table is for endpoints, deployments and the like
+pub trait ScannerFactory: Send + Sync {
+    /// the name users select the scanner by, i.e. 'openai'
+    fn id(&self) -> &str;
+
+    /// environment variables that must be set for the scanner to be usable
+    fn required_env_vars(&self) -> &[&str] {
+        &[]
+    }
+
+    /// how to get the required environment variables, for 'llun doctor'
+    fn setup_hint(&self) -> &str {
+        ""
+    }
+
+    fn create(&self, settings: &ScannerSettings) -> Result<Box<dyn Scanner>, ScannerError>;
+
+    /// the required environment variables that arent currently set
+    fn missing_env_vars(&self) -> Vec<&str> {
+        self.required_env_vars()
+            .iter()
+            .copied()
+            .filter(|var| std::env::var(var).map_or(true, |value| value.is_empty()))
+            .collect()
+    }
+}
+
+/// a ready made scanner registered as is, every scan shares the one instance
+pub(crate) struct SharedScannerFactory {
+    pub(crate) id: String,
+    pub(crate) scanner: Arc<dyn Scanner>,
+}
+
+impl ScannerFactory for SharedScannerFactory {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn create(&self, _settings: &ScannerSettings) -> Result<Box<dyn Scanner>, ScannerError> {
+        Ok(Box::new(Arc::clone(&self.scanner)))
+    }
+}
+
+#[async_trait::async_trait]
+impl Scanner for Arc<dyn Scanner> {
+    async fn scan_files(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+    ) -> Result<Response, ScannerError> {
+        self.as_ref().scan_files(system_prompt, user_prompt, model).await
+    }
+
+    async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+    ) -> Result<String, ScannerError> {
+        self.as_ref().complete(system_prompt, user_prompt, model).await
+    }
+}
@@ -1,66 +1,139 @@
 use crate::api_client::{
-    AvailableScanner, OpenAiClientError, OpenAiScanner, Response, Scanner, ScannerError,
+    AzureOpenAiScannerFactory, OpenAiScannerFactory, Response, Scanner, ScannerError,
+    ScannerFactory, ScannerSettings, SharedScannerFactory, Usage,
 };
 use futures::future::try_join_all;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use tracing::debug;
 
+/// number of independent scans run in production mode before the consistency pass
+/// maybe let the user configure 'n'?
+pub const PRODUCTION_MODE_SAMPLES: usize = 5;
+
+/// ids that were accepted before scanners were registered by name, and what they mean now
+const LEGACY_IDS: &[(&str, &str)] = &[("azureopenai", "azure-openai")];
+
 #[derive(Debug, thiserror::Error)]
 pub enum ScannerManagerError {
-    #[error("Error in OpenAiClient")]
-    OpenAiClientError(#[from] OpenAiClientError),
-    #[error("Chosen scanner not found")]
-    ScannerNotFound(),
+    #[error("Unknown provider '{0}', expected one of: {1}")]
+    ScannerNotFound(String, String),
+    #[error("Provider '{0}' isnt usable")]
+    ScannerUnavailable(String, #[source] Box<ScannerError>),
     #[error("Error whilst scanning")]
     ScannerError(#[from] ScannerError),
-    #[error("No scanners available")]
-    NoScannersAvailable,
 }
 
+impl ScannerManagerError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            ScannerManagerError::ScannerNotFound(..) => Some(
+                "set 'provider' in llun.toml, LLUN_PROVIDER or pass --provider to one of the above".to_string(),
+            ),
+            ScannerManagerError::ScannerUnavailable(_, e) => e
+                .hint()
+                .or_else(|| Some("run 'llun doctor' to see what the provider is missing".to_string())),
+            ScannerManagerError::ScannerError(_) => None,
+        }
+    }
+}
+
+/// how far through a scan we are, for callers that want to show it
+/// each request to the provider is a batch, numbered from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanProgress {
+    /// sent by 'Llun::check' before anything goes to the provider
+    FilesLoaded { files: usize },
+    BatchSent { batch: usize, total: usize },
+    BatchCompleted { batch: usize, total: usize },
+}
+
+/// every scanner llun can use, by id, along with the settings to build them with
+/// built on demand, so a scanner thats missing its secrets only fails if its actually chosen
 pub struct ScannerManager {
-    scanners: HashMap<AvailableScanner, Box<dyn Scanner>>,
+    factories: BTreeMap<String, Arc<dyn ScannerFactory>>,
+    settings: HashMap<String, ScannerSettings>,
+}
+
+impl Default for ScannerManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ScannerManager {
-    pub fn new() -> Result<Self, ScannerManagerError> {
-        let mut scanners: HashMap<AvailableScanner, Box<dyn Scanner>> = HashMap::new();
-
-        Self::try_register_scanner(&mut scanners, AvailableScanner::OpenAi, OpenAiScanner::new);
-        Self::try_register_scanner(
-            &mut scanners,
-            AvailableScanner::AzureOpenAi,
-            OpenAiScanner::new_azure,
-        );
-
-        if scanners.is_empty() {
-            return Err(ScannerManagerError::NoScannersAvailable);
-        }
+    /// the scanners llun ships with
+    pub fn new() -> Self {
+        let mut manager = Self::empty();
+        manager.register_factory(OpenAiScannerFactory);
+        manager.register_factory(AzureOpenAiScannerFactory);
 
-        Ok(Self { scanners })
-    }
-
-    /// spent ages trying to find a way to register mappings -_-
-    /// in the end both gpt and claude offered this as the solution
-    /// supprisingly the only difference was claude having clearer var names?
-    /// im sure this cant be the best method as its ugly as sin
-    /// but for now, it works.
-    fn try_register_scanner<F>(
-        scanners: &mut HashMap<AvailableScanner, Box<dyn Scanner>>,
-        scanner_type: AvailableScanner,
-        constructor: F,
-    ) where
-        F: FnOnce() -> Result<OpenAiScanner, OpenAiClientError>,
-    {
-        match constructor() {
-            Ok(scanner) => {
-                scanners.insert(scanner_type, Box::new(scanner));
-            }
-            Err(e) => {
-                debug!("Failed to initialize scanner: {}", e);
-            }
+        manager
+    }
+
+    /// no scanners at all, for when only your own should be used
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+            settings: HashMap::new(),
         }
     }
 
+    /// settings for each scanner by id, i.e. the '[scanners]' tables from the tomls
+    pub fn with_settings(mut self, settings: HashMap<String, ScannerSettings>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// add a scanner, replacing whatever was registered under its id
+    pub fn register_factory(&mut self, factory: impl ScannerFactory + 'static) {
+        debug!("Registering scanner '{}'", factory.id());
+        self.factories
+            .insert(factory.id().to_string(), Arc::new(factory));
+    }
+
+    /// add a ready made scanner under the given id, i.e. a MockScanner
+    pub fn register_scanner(&mut self, id: impl Into<String>, scanner: impl Scanner + 'static) {
+        self.register_factory(SharedScannerFactory {
+            id: id.into(),
+            scanner: Arc::new(scanner),
+        });
+    }
+
+    /// the id of every registered scanner, in order
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    /// every registered scanner factory, ordered by id
+    pub fn factories(&self) -> impl Iterator<Item = &dyn ScannerFactory> {
+        self.factories.values().map(|factory| factory.as_ref())
+    }
+
+    /// the id a user gave, as its registered under now
+    pub fn canonical_id(id: &str) -> &str {
+        LEGACY_IDS
+            .iter()
+            .find(|(legacy, _)| *legacy == id)
+            .map_or(id, |(_, current)| current)
+    }
+
+    /// build the chosen scanner from its factory and settings
+    pub fn scanner(&self, id: &str) -> Result<Box<dyn Scanner>, ScannerManagerError> {
+        let id = Self::canonical_id(id);
+        let factory = self.factories.get(id).ok_or_else(|| {
+            ScannerManagerError::ScannerNotFound(
+                id.to_string(),
+                self.ids().collect::<Vec<_>>().join(", "),
+            )
+        })?;
+        let settings = self.settings.get(id).cloned().unwrap_or_default();
+
+        factory
+            .create(&settings)
+            .map_err(|e| ScannerManagerError::ScannerUnavailable(id.to_string(), Box::new(e)))
+    }
+
     /// use your chosen scanner (its open ai isnt you normie)
     /// to perform a scan
     pub async fn run_scan(
@@ -69,43 +142,184 @@ impl ScannerManager {
         user_prompt: &str,
         model: &str,
         consistency_prompt: &str,
-        scanner: AvailableScanner,
+        scanner: &str,
+        production_mode: bool,
+    ) -> Result<Response, ScannerManagerError> {
+        self.run_scan_with_progress(
+            system_prompt,
+            user_prompt,
+            model,
+            consistency_prompt,
+            scanner,
+            production_mode,
+            &|_| {},
+        )
+        .await
+    }
+
+    /// the same as 'run_scan', telling 'on_progress' as each request is sent and answered
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_scan_with_progress(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+        consistency_prompt: &str,
+        scanner: &str,
+        production_mode: bool,
+        on_progress: &(dyn Fn(ScanProgress) + Send + Sync),
+    ) -> Result<Response, ScannerManagerError> {
+        let chosen_scanner = self.scanner(scanner)?;
+
+        Self::scan_with(
+            chosen_scanner.as_ref(),
+            system_prompt,
+            user_prompt,
+            model,
+            consistency_prompt,
+            production_mode,
+            on_progress,
+        )
+        .await
+    }
+
+    /// scan with a scanner that isnt registered, i.e. one handed to 'Llun::builder()'
+    pub async fn scan_with(
+        chosen_scanner: &dyn Scanner,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+        consistency_prompt: &str,
         production_mode: bool,
+        on_progress: &(dyn Fn(ScanProgress) + Send + Sync),
     ) -> Result<Response, ScannerManagerError> {
-        let chosen_scanner = self
-            .scanners
-            .get(&scanner)
-            .ok_or_else(ScannerManagerError::ScannerNotFound)?;
+        let total = Self::request_count(production_mode);
 
         if production_mode {
-            // maybe let the user configure 'n'?
-            let futures =
-                (0..5).map(|_| chosen_scanner.scan_files(system_prompt, user_prompt, model));
+            let futures = (1..=PRODUCTION_MODE_SAMPLES).map(|batch| async move {
+                on_progress(ScanProgress::BatchSent { batch, total });
+                let result = chosen_scanner.scan_files(system_prompt, user_prompt, model).await;
+                on_progress(ScanProgress::BatchCompleted { batch, total });
+                result
+            });
             let results = try_join_all(futures).await?;
-            let combined = self.combine_responses(results);
-
-            Ok(chosen_scanner
-                .scan_files(
-                    consistency_prompt,
-                    &serde_json::to_string(&combined).unwrap(),
-                    model,
-                )
-                .await?)
+            let sample_usage = results
+                .iter()
+                .map(|result| result.usage)
+                .fold(None, Usage::combine);
+            let combined = Self::combine_responses(results);
+
+            on_progress(ScanProgress::BatchSent { batch: total, total });
+            let combined = serde_json::to_string(&combined).map_err(ScannerError::from)?;
+            let mut response = chosen_scanner
+                .scan_files(consistency_prompt, &combined, model)
+                .await?;
+            on_progress(ScanProgress::BatchCompleted { batch: total, total });
+
+            // the samples were paid for too, not just the pass that decided between them
+            response.usage = Usage::combine(sample_usage, response.usage);
+            Ok(response)
         } else {
-            Ok(chosen_scanner
+            on_progress(ScanProgress::BatchSent { batch: 1, total });
+            let response = chosen_scanner
                 .scan_files(system_prompt, user_prompt, model)
-                .await?)
+                .await?;
+            on_progress(ScanProgress::BatchCompleted { batch: 1, total });
+
+            Ok(response)
+        }
+    }
+
+    /// ask your chosen scanner for a free text explanation rather than a scan
+    pub async fn run_explanation(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: &str,
+        scanner: &str,
+    ) -> Result<String, ScannerManagerError> {
+        let chosen_scanner = self.scanner(scanner)?;
+
+        Ok(chosen_scanner
+            .complete(system_prompt, user_prompt, model)
+            .await?)
+    }
+
+    /// send the smallest request we can, to check the provider is reachable and accepts the model
+    pub async fn check_connection(
+        &self,
+        model: &str,
+        scanner: &str,
+    ) -> Result<(), ScannerManagerError> {
+        self.run_explanation("Reply with the single word 'ok'.", "ok?", model, scanner)
+            .await
+            .map(|_| ())
+    }
+
+    /// how many requests a single scan will send to the provider
+    pub fn request_count(production_mode: bool) -> usize {
+        if production_mode {
+            PRODUCTION_MODE_SAMPLES + 1 // plus the consistency pass
+        } else {
+            1
         }
     }
 
     /// merge many async responses into a single Response object
-    fn combine_responses(&self, responses: Vec<Response>) -> Response {
+    fn combine_responses(responses: Vec<Response>) -> Response {
         let mut all_issues = Vec::new();
         for response in responses {
             all_issues.extend(response.detected_issues);
         }
         Response {
             detected_issues: all_issues,
+            usage: None,
         }
     }
 }
+
+/// every built-in provider id as a clap value, so typos are caught (and completed) before anything runs
+/// ids are matched whatever their case, and the legacy ids are still accepted without being offered
+#[cfg(feature = "clap")]
+pub fn provider_value_parser() -> ProviderValueParser {
+    let scanner_manager = ScannerManager::new();
+    let values: Vec<clap::builder::PossibleValue> = scanner_manager
+        .ids()
+        .map(|id| {
+            let legacy_ids = LEGACY_IDS
+                .iter()
+                .filter(|(_, current)| *current == id)
+                .map(|(legacy, _)| *legacy);
+            clap::builder::PossibleValue::new(id.to_string()).aliases(legacy_ids)
+        })
+        .collect();
+
+    ProviderValueParser(clap::builder::PossibleValuesParser::new(values))
+}
+
+/// a provider id, lowercased before its checked against the built-in ids and handed back as its current id
+#[cfg(feature = "clap")]
+#[derive(Clone)]
+pub struct ProviderValueParser(clap::builder::PossibleValuesParser);
+
+#[cfg(feature = "clap")]
+impl clap::builder::TypedValueParser for ProviderValueParser {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &std::ffi::OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value.to_string_lossy().to_lowercase();
+        let id = self.0.parse_ref(cmd, arg, std::ffi::OsStr::new(&value))?;
+        Ok(ScannerManager::canonical_id(&id).to_string())
+    }
+
+    fn possible_values(
+        &self,
+    ) -> Option<Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_>> {
+        self.0.possible_values()
+    }
+}
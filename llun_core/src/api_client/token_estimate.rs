@@ -0,0 +1,8 @@
+/// rough average for english text and code across the openai tokenisers
+/// its an estimate, not a bill - the provider has the final say
+const CHARS_PER_TOKEN: usize = 4;
+
+/// estimate how many tokens a piece of text will cost to send
+pub fn estimate_tokens(text: &str) -> u64 {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN) as u64
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::AddAssign;
+use std::time::Duration;
+
+use crate::api_client::PriceTable;
+
+/// what a scan cost, summed over every request sent to the provider
+/// latency is summed rather than wall time, production mode sends its samples at once
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub requests: usize,
+    /// missing if any of the requests went to a provider that didnt report its token counts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_tokens: Option<u64>,
+    pub latency_ms: u64,
+    /// dollars, only known if the models price is in the price table
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+impl Usage {
+    /// a single request to the provider, with its token counts if the provider reported them
+    pub fn request(
+        prompt_tokens: Option<u64>,
+        completion_tokens: Option<u64>,
+        latency: Duration,
+    ) -> Self {
+        Self {
+            requests: 1,
+            prompt_tokens,
+            completion_tokens,
+            latency_ms: latency.as_millis() as u64,
+            cost: None,
+        }
+    }
+
+    /// the total of two runs, either of which might not have reported any usage at all
+    pub fn combine(total: Option<Usage>, usage: Option<Usage>) -> Option<Usage> {
+        match (total, usage) {
+            (Some(mut total), Some(usage)) => {
+                total += usage;
+                Some(total)
+            }
+            (total, usage) => total.or(usage),
+        }
+    }
+
+    pub fn total_tokens(&self) -> Option<u64> {
+        Some(self.prompt_tokens? + self.completion_tokens?)
+    }
+
+    /// price the tokens used on the given model, leaving the cost unknown if the model isnt priced
+    /// or the tokens werent reported, guessing zero would make an unknown cost look free
+    pub fn priced(mut self, model: &str, pricing: &PriceTable) -> Self {
+        self.cost = self
+            .prompt_tokens
+            .zip(self.completion_tokens)
+            .and_then(|(prompt_tokens, completion_tokens)| {
+                pricing.estimate_cost(model, prompt_tokens, completion_tokens)
+            });
+        self
+    }
+}
+
+/// adding up a run, i.e. each production mode sample or each config group
+/// if any part of it wasnt counted or priced then neither is the whole, a partial total would undersell it
+impl AddAssign for Usage {
+    fn add_assign(&mut self, other: Usage) {
+        if self.requests == 0 {
+            *self = other;
+            return;
+        }
+
+        self.requests += other.requests;
+        self.prompt_tokens = self.prompt_tokens.zip(other.prompt_tokens).map(|(a, b)| a + b);
+        self.completion_tokens = self
+            .completion_tokens
+            .zip(other.completion_tokens)
+            .map(|(a, b)| a + b);
+        self.latency_ms += other.latency_ms;
+        self.cost = self.cost.zip(other.cost).map(|(cost, other_cost)| cost + other_cost);
+    }
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.prompt_tokens, self.completion_tokens) {
+            (Some(prompt_tokens), Some(completion_tokens)) => write!(
+                f,
+                "{} prompt + {} completion tokens",
+                prompt_tokens, completion_tokens
+            )?,
+            _ => write!(f, "unreported tokens")?,
+        }
+        write!(
+            f,
+            " over {} request(s), {:.1}s waiting on the provider",
+            self.requests,
+            self.latency_ms as f64 / 1000.0
+        )?;
+        match (self.cost, self.total_tokens()) {
+            (Some(cost), _) => write!(f, ", ${:.4}", cost),
+            (None, Some(_)) => write!(f, ", cost unknown (see [pricing] in llun.toml)"),
+            (None, None) => write!(f, ", cost unknown (the provider didnt report its token usage)"),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::debug;
+
+use crate::api_client::{Response, ResponseError};
+use crate::files::normalise_path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BaselineError {
+    #[error("Failed to load baseline")]
+    LoadError(#[from] ResponseError),
+}
+
+/// a snapshot of already known issues, so only new ones get reported
+/// issues are matched on file and rule, as the models wording wont be the same twice
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    known_issues: HashSet<(String, String)>,
+}
+
+impl Baseline {
+    /// treat every issue in a response as already known
+    pub fn new(response: &Response) -> Self {
+        let known_issues = response
+            .detected_issues
+            .iter()
+            .map(|issue| Self::key(&issue.file_path, &issue.rule_code))
+            .collect();
+
+        Self { known_issues }
+    }
+
+    /// load a baseline from results previously saved as json
+    pub fn from_file(path: &Path) -> Result<Self, BaselineError> {
+        debug!("Loading baseline from {:?}", path);
+        Ok(Self::new(&Response::from_file(path)?))
+    }
+
+    /// whether an issue was already present in the baseline
+    pub fn contains(&self, file_path: &str, rule_code: &str) -> bool {
+        self.known_issues.contains(&Self::key(file_path, rule_code))
+    }
+
+    /// drop every issue already present in the baseline
+    pub fn apply(&self, mut response: Response) -> Response {
+        response
+            .detected_issues
+            .retain(|issue| !self.contains(&issue.file_path, &issue.rule_code));
+
+        response
+    }
+
+    fn key(file_path: &str, rule_code: &str) -> (String, String) {
+        (normalise_path(file_path).to_string(), rule_code.to_string())
+    }
+}
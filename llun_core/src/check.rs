@@ -0,0 +1,9 @@
+pub mod check_config;
+pub mod llun;
+pub mod llun_builder;
+pub mod report;
+
+pub use check_config::CheckConfig;
+pub use llun::{CheckError, Llun, PreparedCheck};
+pub use llun_builder::LlunBuilder;
+pub use report::{REPORT_SCHEMA_VERSION, Report, ReportedFile, ReportedRule};
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use crate::api_client::{PriceTable, ScannerSettings};
+use crate::rules::Severity;
+
+/// everything a check needs to know, shared by the cli, the mcp server and anyone embedding llun
+/// with the 'clap' feature these double as the 'llun check' arguments
+/// NOTE: skip_serialisation_if must be set to allow toml values to
+/// not be overwritten by emty values (flags included, or 'false' always wins)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::Args))]
+pub struct CheckConfig {
+    /// paths from root to desired directory or specific file, or '-' to read content from stdin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<PathBuf>,
+
+    /// paths otherwise targetted by 'path' that should be skipped from scanning
+    #[cfg_attr(feature = "clap", arg(short, long))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<PathBuf>,
+
+    /// rules to utilise in the scan (overrides default values)
+    #[cfg_attr(feature = "clap", arg(short, long))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub select: Vec<String>,
+
+    /// rules to add to the default to utilise in the scan
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extend_select: Vec<String>,
+
+    /// rules to ignore from the default list
+    #[cfg_attr(feature = "clap", arg(short, long))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+
+    /// openai model to use under the hood
+    #[cfg_attr(feature = "clap", arg(short = 'M', long))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// default ignore all files in the gitignore, to avoid leaking secrets etc...
+    #[cfg_attr(feature = "clap", arg(short, long, action = clap::ArgAction::SetTrue))]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_respect_gitignore: bool,
+
+    /// type of output to give
+    #[cfg_attr(feature = "clap", arg(short, long, value_parser = crate::formatters::output_manager::output_format_value_parser()))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_format: Vec<String>,
+
+    /// minijinja template file rendered by the 'template' output format
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<PathBuf>,
+
+    /// write the 'json' format as just the detected issues, without the run metadata around them
+    #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::SetTrue))]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub legacy_json: bool,
+
+    /// llm provider
+    #[cfg_attr(feature = "clap", arg(long, value_parser = crate::api_client::scanner_manager::provider_value_parser()))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+
+    /// user provided context (i.e. commit message) to help llun understand the point
+    #[cfg_attr(feature = "clap", arg(short, long))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+
+    /// utilise USC to improve the reliability of the model response
+    #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::SetTrue))]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub production_mode: bool,
+
+    /// send the files in batches of at most this many, one request each (1 scans every file on its own)
+    /// files unchanged since they were last scanned this way are answered from the cache instead
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<NonZeroUsize>,
+
+    /// send every file, neither reading nor updating the results cached in .llun_cache
+    #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::SetTrue))]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_cache: bool,
+
+    /// files to ignore certain rule violations on i.e. 'main.py::RULE01'
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub per_file_ignores: Vec<String>,
+
+    /// only check files staged for commit, reading their content from the git index
+    #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::SetTrue))]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub staged: bool,
+
+    /// exit with a failure if any issue is at least this severe (info, warning, error)
+    #[cfg_attr(feature = "clap", arg(long))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on: Option<Severity>,
+
+    /// severity to report each rules issues at instead of the rules own i.e. '[severity] SOLID01 = "error"'
+    /// only settable in the tomls, nested configs add to (or replace) the overrides above them
+    #[cfg_attr(feature = "clap", arg(skip))]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub severity: HashMap<String, Severity>,
+
+    /// dollars per million tokens for each model, only settable in the tomls
+    #[cfg_attr(feature = "clap", arg(skip))]
+    #[serde(default, skip_serializing_if = "PriceTable::is_empty")]
+    pub pricing: PriceTable,
+
+    /// settings for each provider by id i.e. '[scanners.azure-openai]', only settable in the tomls
+    #[cfg_attr(feature = "clap", arg(skip))]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scanners: HashMap<String, ScannerSettings>,
+}
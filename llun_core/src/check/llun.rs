@@ -0,0 +1,463 @@
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::api_client::{
+    DetectedIssue, PromptManager, Response, ScanProgress, Scanner, ScannerManager,
+};
+use crate::check::{CheckConfig, LlunBuilder, Report};
+use crate::errors::{ErrorReport, LlunCoreError};
+use crate::files::{File, FileManager, FileSet, GitIndex, normalise_path};
+use crate::formatters::OutputManager;
+use crate::per_file_ignorer::PerFileIgnorer;
+use crate::result_cache::{CACHE_DIR, CacheScope, ResultCache};
+use crate::rules::{RuleManager, RuleSet};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CheckError {
+    #[error("No model is configured")]
+    MissingModel,
+    #[error("No provider is configured")]
+    MissingProvider,
+    #[error("Invalid check configuration")]
+    InvalidConfig(#[from] Box<figment::Error>),
+}
+
+impl CheckError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            CheckError::MissingModel => Some(
+                "set 'model' in llun.toml, LLUN_MODEL or pass --model, i.e. 'gpt-4o'".to_string(),
+            ),
+            CheckError::MissingProvider => Some(
+                "set 'provider' in llun.toml, LLUN_PROVIDER or pass --provider".to_string(),
+            ),
+            CheckError::InvalidConfig(_) => None,
+        }
+    }
+}
+
+/// the rules, files and prompts a check will send, built before anything is sent
+pub struct PreparedCheck {
+    pub rules: RuleSet,
+    pub files: FileSet,
+    pub prompts: PromptManager,
+}
+
+/// llun in one place: select the rules and files, scan them, and format what was found
+/// build one with 'Llun::builder()'
+pub struct Llun {
+    pub(crate) config: CheckConfig,
+    /// files that arent (or shouldnt be read from) on disk, i.e. stdin or an unsaved buffer
+    pub(crate) files: Vec<File>,
+    /// used instead of the configured provider if set, i.e. a MockScanner
+    pub(crate) scanner: Option<Box<dyn Scanner>>,
+    /// every scanner 'provider' can pick from
+    pub(crate) scanner_manager: ScannerManager,
+    pub(crate) output_manager: OutputManager,
+    /// where paths, user rules and the cache are found, the working directory if unset
+    pub(crate) root: Option<PathBuf>,
+}
+
+impl Llun {
+    pub fn builder() -> LlunBuilder {
+        LlunBuilder::default()
+    }
+
+    pub fn config(&self) -> &CheckConfig {
+        &self.config
+    }
+
+    /// load the selected rules and files and build the prompts, without contacting the provider
+    pub fn prepare(&self) -> Result<PreparedCheck, LlunCoreError> {
+        info!("Loading selected rules...");
+        let rule_manager = RuleManager::in_dir(self.root())?;
+        let rules = rule_manager.load_from_cli(
+            self.config.select.clone(),
+            self.config.extend_select.clone(),
+            self.config.ignore.clone(),
+        )?;
+
+        info!("Reading selected files...");
+        let files = self.load_files()?;
+        let prompts = PromptManager::new(&rules, &files, &self.config.context)?;
+
+        Ok(PreparedCheck {
+            rules,
+            files,
+            prompts,
+        })
+    }
+
+    /// run the check from start to finish
+    pub async fn check(&self) -> Result<Report, LlunCoreError> {
+        self.check_with_progress(&|_| {}).await
+    }
+
+    /// the same as 'check', telling 'on_progress' once the files are loaded and as each request goes out
+    pub async fn check_with_progress(
+        &self,
+        on_progress: &(dyn Fn(ScanProgress) + Send + Sync),
+    ) -> Result<Report, LlunCoreError> {
+        let model = self.config.model.as_deref().ok_or(CheckError::MissingModel)?;
+        let per_file_ignorer = PerFileIgnorer::new(self.config.per_file_ignores.clone())?;
+        let PreparedCheck {
+            rules,
+            files,
+            prompts,
+        } = self.prepare()?;
+        on_progress(ScanProgress::FilesLoaded { files: files.len() });
+
+        info!("Querying selected endpoint...");
+        let mut model_response = match self.config.batch_size {
+            Some(batch_size) => {
+                self.scan_batches(&rules, &files, batch_size.get(), model, on_progress)
+                    .await?
+            }
+            None => self.scan(&prompts, model, on_progress).await?,
+        };
+
+        model_response.usage = model_response
+            .usage
+            .map(|usage| usage.priced(model, &self.config.pricing));
+        if let Some(usage) = &model_response.usage {
+            info!("Used {}", usage);
+        }
+
+        Ok(Report::new(
+            self.apply_severity_overrides(
+                rules.apply_severities(per_file_ignorer.apply_ignores(model_response)),
+            ),
+            model,
+            self.provider(),
+            &rules,
+            &files,
+            GitIndex::head_commit(self.root()),
+        ))
+    }
+
+    /// send the prompts to the scanner handed to the builder, or the configured provider if there wasnt one
+    async fn scan(
+        &self,
+        prompts: &PromptManager,
+        model: &str,
+        on_progress: &(dyn Fn(ScanProgress) + Send + Sync),
+    ) -> Result<Response, LlunCoreError> {
+        let response = match &self.scanner {
+            Some(scanner) => {
+                ScannerManager::scan_with(
+                    scanner.as_ref(),
+                    &prompts.system_prompt_scan,
+                    &prompts.user_prompt,
+                    model,
+                    &prompts.system_prompt_consistency,
+                    self.config.production_mode,
+                    on_progress,
+                )
+                .await?
+            }
+            None => {
+                let provider = self.provider().ok_or(CheckError::MissingProvider)?;
+                self.scanner_manager
+                    .run_scan_with_progress(
+                        &prompts.system_prompt_scan,
+                        &prompts.user_prompt,
+                        model,
+                        &prompts.system_prompt_consistency,
+                        provider,
+                        self.config.production_mode,
+                        on_progress,
+                    )
+                    .await?
+            }
+        };
+
+        Ok(response)
+    }
+
+    /// scan the files a batch at a time, answering any that havent changed from the cache
+    /// each batch gets its own prompt, so what the model says about a file only depends on its batch
+    async fn scan_batches(
+        &self,
+        rules: &RuleSet,
+        files: &FileSet,
+        batch_size: usize,
+        model: &str,
+        on_progress: &(dyn Fn(ScanProgress) + Send + Sync),
+    ) -> Result<Response, LlunCoreError> {
+        let prompt_version = PromptManager::version()?;
+        let cache = (!self.config.no_cache).then(|| {
+            ResultCache::new(
+                self.resolve(Path::new(CACHE_DIR)),
+                &CacheScope {
+                    rules,
+                    model,
+                    provider: self.provider(),
+                    prompt_version: &prompt_version,
+                    context: self.config.context.as_deref(),
+                    production_mode: self.config.production_mode,
+                },
+            )
+        });
+
+        let mut response = Response::default();
+        let mut uncached = Vec::new();
+        for file in files.iter() {
+            match cache.as_ref().and_then(|cache| cache.get(file)) {
+                Some(issues) => response.detected_issues.extend(issues),
+                None => uncached.push(file),
+            }
+        }
+        if cache.is_some() {
+            info!(
+                "{} file(s) unchanged since they were cached, {} to scan",
+                files.len() - uncached.len(),
+                uncached.len()
+            );
+        }
+
+        let requests_per_batch = ScannerManager::request_count(self.config.production_mode);
+        let batches: Vec<&[&File]> = uncached.chunks(batch_size).collect();
+        let total = batches.len() * requests_per_batch;
+        for (index, batch) in batches.into_iter().enumerate() {
+            let mut batch_files = FileSet::new();
+            for file in batch {
+                batch_files.add_file((*file).clone());
+            }
+            let prompts = PromptManager::new(rules, &batch_files, &self.config.context)?;
+
+            // each batch numbers its requests from 1, so carry on from wherever the last left off
+            let offset = index * requests_per_batch;
+            let batch_response = self
+                .scan(&prompts, model, &|event| {
+                    on_progress(match event {
+                        ScanProgress::BatchSent { batch, .. } => ScanProgress::BatchSent {
+                            batch: offset + batch,
+                            total,
+                        },
+                        ScanProgress::BatchCompleted { batch, .. } => {
+                            ScanProgress::BatchCompleted {
+                                batch: offset + batch,
+                                total,
+                            }
+                        }
+                        event => event,
+                    })
+                })
+                .await?;
+
+            if let Some(cache) = &cache {
+                Self::cache_batch(cache, batch, &batch_response);
+            }
+
+            response.detected_issues.extend(batch_response.detected_issues);
+            response.add_usage(batch_response.usage);
+        }
+
+        Ok(response)
+    }
+
+    /// remember what was found in each file of a batch, unless the model put an issue against a file
+    /// that wasnt in it. theres no telling which file it meant, so caching the batch could lose the issue
+    fn cache_batch(cache: &ResultCache, batch: &[&File], response: &Response) {
+        let unmatched = response.detected_issues.iter().find(|issue| {
+            !batch
+                .iter()
+                .any(|file| Self::same_file(&issue.file_path, &file.name))
+        });
+        if let Some(issue) = unmatched {
+            warn!(
+                "Not caching this batch, an issue was reported in '{}' which wasnt part of it",
+                issue.file_path
+            );
+            return;
+        }
+
+        for file in batch {
+            let issues: Vec<DetectedIssue> = response
+                .detected_issues
+                .iter()
+                .filter(|issue| Self::same_file(&issue.file_path, &file.name))
+                .cloned()
+                .collect();
+            // a cache that cant be written to only costs the next run, not this one
+            if let Err(e) = cache.put(file, &issues) {
+                warn!("{}", ErrorReport::new(&e));
+            }
+        }
+    }
+
+    /// the users own severities win over the rules, so a package can be stricter (or laxer) than the rest
+    fn apply_severity_overrides(&self, mut response: Response) -> Response {
+        for issue in &mut response.detected_issues {
+            if let Some(severity) = self.config.severity.get(&issue.rule_code) {
+                issue.severity = *severity;
+            }
+        }
+
+        response
+    }
+
+    /// the provider the check is sent to, a scanner handed over by an embedder wasnt picked by provider
+    fn provider(&self) -> Option<&str> {
+        match &self.scanner {
+            Some(_) => None,
+            None => self.config.provider.as_deref(),
+        }
+    }
+
+    fn root(&self) -> &Path {
+        self.root.as_deref().unwrap_or(Path::new("."))
+    }
+
+    /// a path from the config, as it is if theres no root so files keep the names they were given
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match &self.root {
+            Some(root) => root.join(path),
+            None => path.to_path_buf(),
+        }
+    }
+
+    /// a path found under the root, named as it would be if llun had been run from there
+    fn relative(&self, path: &Path) -> PathBuf {
+        match &self.root {
+            Some(root) => path.strip_prefix(root).unwrap_or(path).to_path_buf(),
+            None => path.to_path_buf(),
+        }
+    }
+
+    fn same_file(file_path: &str, name: &str) -> bool {
+        normalise_path(file_path) == normalise_path(name)
+    }
+
+    /// the report in a single format
+    pub fn format(&self, report: &Report, output_format: &str) -> Result<String, LlunCoreError> {
+        Ok(self.output_manager.format_report(report, output_format)?)
+    }
+
+    /// print the report in each of the configured output formats
+    pub fn output(&self, report: &Report) -> Result<(), LlunCoreError> {
+        Ok(self
+            .output_manager
+            .process_report(report, &self.config.output_format)?)
+    }
+
+    /// every file the config selects, with any in-memory files standing in for the disk copy of the same name
+    fn load_files(&self) -> Result<FileSet, LlunCoreError> {
+        let in_memory: Vec<PathBuf> = self.files.iter().map(|file| PathBuf::from(&file.name)).collect();
+        let paths: Vec<PathBuf> = self
+            .config
+            .path
+            .iter()
+            .filter(|path| !in_memory.contains(path))
+            .map(|path| self.resolve(path))
+            .collect();
+        let exclude: Vec<PathBuf> = self.config.exclude.iter().map(|path| self.resolve(path)).collect();
+
+        let mut file_paths =
+            FileManager::collect_from_cli(paths, exclude, self.config.no_respect_gitignore)?;
+        file_paths.retain(|path| !in_memory.contains(&self.relative(path)));
+
+        let disk_files = if self.config.staged {
+            let index = GitIndex::open_in(self.root())?;
+            file_paths.retain(|path| index.is_staged(path));
+            FileManager::load_from_index(file_paths, &index)?
+        } else {
+            FileManager::load_from_files(file_paths)?
+        };
+
+        let mut files = FileSet::new();
+        for file in disk_files {
+            files.add_file(File {
+                name: self.relative(Path::new(&file.name)).to_string_lossy().to_string(),
+                content: file.content,
+            });
+        }
+        for file in &self.files {
+            files.add_file(file.clone());
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)] // figment::Jail closures have to return a bare figment::Error
+mod tests {
+    use super::*;
+    use figment::Jail;
+
+    fn file(name: &str) -> File {
+        File {
+            name: name.to_string(),
+            content: format!("# {}", name),
+        }
+    }
+
+    fn issue(file_path: &str) -> DetectedIssue {
+        DetectedIssue {
+            rule_code: "SOLID01".to_string(),
+            name: "Single Responsibility".to_string(),
+            file_path: file_path.to_string(),
+            brief_description: "does too much".to_string(),
+            explanation: "".to_string(),
+            suggested_alternative: "".to_string(),
+            code_snippet: "".to_string(),
+            severity: Default::default(),
+        }
+    }
+
+    fn cache() -> ResultCache {
+        let rules = RuleManager::new()
+            .and_then(|manager| {
+                manager.load_from_cli(vec!["SOLID01".to_string()], Vec::new(), Vec::new())
+            })
+            .unwrap();
+        ResultCache::new(
+            CACHE_DIR,
+            &CacheScope {
+                rules: &rules,
+                model: "gpt-4o",
+                provider: Some("openai"),
+                prompt_version: "1",
+                context: None,
+                production_mode: false,
+            },
+        )
+    }
+
+    #[test]
+    fn batch_is_cached_per_file() {
+        Jail::expect_with(|_jail| {
+            let cache = cache();
+            let (a, b) = (file("a.py"), file("b.py"));
+            let response = Response {
+                detected_issues: vec![issue("./a.py")],
+                ..Default::default()
+            };
+
+            Llun::cache_batch(&cache, &[&a, &b], &response);
+
+            assert_eq!(cache.get(&a).map(|issues| issues.len()), Some(1));
+            assert_eq!(cache.get(&b).map(|issues| issues.len()), Some(0));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn batch_with_an_unknown_file_isnt_cached() {
+        Jail::expect_with(|_jail| {
+            let cache = cache();
+            let (a, b) = (file("a.py"), file("b.py"));
+            let response = Response {
+                detected_issues: vec![issue("a.py"), issue("src/b.py")],
+                ..Default::default()
+            };
+
+            Llun::cache_batch(&cache, &[&a, &b], &response);
+
+            assert!(cache.get(&a).is_none());
+            assert!(cache.get(&b).is_none());
+            Ok(())
+        });
+    }
+}
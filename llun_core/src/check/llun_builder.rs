@@ -0,0 +1,139 @@
+use figment::{
+    Figment,
+    providers::{Format, Serialized, Toml},
+};
+use std::path::PathBuf;
+
+use crate::api_client::{Scanner, ScannerFactory, ScannerManager};
+use crate::check::{CheckConfig, CheckError, Llun};
+use crate::data::DEFAULT_CONFIG;
+use crate::errors::LlunCoreError;
+use crate::files::File;
+use crate::formatters::{OutputFormatter, OutputManager};
+
+/// put together a Llun, anything left unset falls back to llun's defaults
+/// i.e. 'Llun::builder().path("src").model("gpt-4o").build()?'
+#[derive(Default)]
+pub struct LlunBuilder {
+    config: CheckConfig,
+    files: Vec<File>,
+    scanner: Option<Box<dyn Scanner>>,
+    scanner_manager: ScannerManager,
+    formatters: Vec<(String, Box<dyn OutputFormatter>)>,
+    root: Option<PathBuf>,
+}
+
+impl LlunBuilder {
+    /// start from a whole config, i.e. one already layered from the users tomls
+    pub fn config(mut self, config: CheckConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// a file or directory to check, can be given more than once
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.path.push(path.into());
+        self
+    }
+
+    /// the project to check, paths, excludes, user rules and the cache are all found from here
+    /// rather than the working directory. files are still named relative to it
+    pub fn root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// rules or rule families to check against, replacing the default selection
+    pub fn select(mut self, rules: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.config.select = rules.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.config.model = Some(model.into());
+        self
+    }
+
+    /// id of the registered scanner to use, i.e. 'openai'
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.config.provider = Some(provider.into());
+        self
+    }
+
+    /// what the code is for, to help llun understand the point of it
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.config.context = Some(context.into());
+        self
+    }
+
+    pub fn production_mode(mut self, production_mode: bool) -> Self {
+        self.config.production_mode = production_mode;
+        self
+    }
+
+    /// check content that isnt on disk, replacing the disk copy if a selected path has the same name
+    pub fn file(mut self, file: File) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// scan with this rather than the configured provider, i.e. a MockScanner
+    pub fn scanner(mut self, scanner: impl Scanner + 'static) -> Self {
+        self.scanner = Some(Box::new(scanner));
+        self
+    }
+
+    /// make another scanner available to pick with 'provider', alongside the built-in ones
+    pub fn register_factory(mut self, factory: impl ScannerFactory + 'static) -> Self {
+        self.scanner_manager.register_factory(factory);
+        self
+    }
+
+    /// make a ready made scanner available to pick with 'provider', under the given id
+    pub fn register_scanner(mut self, id: impl Into<String>, scanner: impl Scanner + 'static) -> Self {
+        self.scanner_manager.register_scanner(id, scanner);
+        self
+    }
+
+    /// make a formatter available as an output format under the given id, replacing llun's own if the id is taken
+    pub fn formatter(
+        mut self,
+        output_format: impl Into<String>,
+        formatter: impl OutputFormatter + 'static,
+    ) -> Self {
+        self.formatters.push((output_format.into(), Box::new(formatter)));
+        self
+    }
+
+    /// minijinja template file for the 'template' output format
+    pub fn template(mut self, template: impl Into<PathBuf>) -> Self {
+        self.config.template = Some(template.into());
+        self
+    }
+
+    pub fn build(self) -> Result<Llun, LlunCoreError> {
+        let config: CheckConfig = Figment::new()
+            .merge(Toml::string(DEFAULT_CONFIG))
+            .merge(Serialized::defaults(&self.config))
+            .extract()
+            .map_err(|e| CheckError::InvalidConfig(Box::new(e)))?;
+
+        let mut output_manager = OutputManager::new()
+            .with_template(config.template.as_deref())?
+            .with_legacy_json(config.legacy_json);
+        for (output_format, formatter) in self.formatters {
+            output_manager.register(output_format, formatter);
+        }
+
+        let scanner_manager = self.scanner_manager.with_settings(config.scanners.clone());
+
+        Ok(Llun {
+            config,
+            files: self.files,
+            scanner: self.scanner,
+            scanner_manager,
+            output_manager,
+            root: self.root,
+        })
+    }
+}
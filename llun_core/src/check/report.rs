@@ -0,0 +1,137 @@
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::api_client::{Response, ResponseError};
+use crate::files::FileSet;
+use crate::rules::{RuleSet, Severity};
+
+/// bumped whenever a field of the report is renamed or removed, adding one doesnt need it
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// a file sent to the scanner, with the hash of the content it was sent with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportedFile {
+    pub name: String,
+    pub hash: String,
+}
+
+/// a rule the files were checked against, with the hash of the rule as the model saw it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportedRule {
+    pub rule_code: String,
+    pub hash: String,
+}
+
+/// the outcome of a check: what was found, what was looked at to find it and what did the looking
+/// this is the shape of the json output, the issues (and usage) sit at the top level
+/// so a saved report loads as a plain 'Response' too i.e. for baselines
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub schema_version: u32,
+    pub llun_version: String,
+    /// when the check finished, as rfc 3339 in utc
+    pub generated_at: String,
+    /// the commit checked out when the check ran, if there was one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    pub model: String,
+    /// missing when an embedder handed llun its own scanner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// every rule the files were checked against
+    pub rules: Vec<ReportedRule>,
+    /// every file sent to the scanner
+    pub files: Vec<ReportedFile>,
+    #[serde(flatten)]
+    pub response: Response,
+}
+
+impl Report {
+    /// the report of a check that has just finished
+    pub fn new(
+        response: Response,
+        model: &str,
+        provider: Option<&str>,
+        rules: &RuleSet,
+        files: &FileSet,
+        git_commit: Option<String>,
+    ) -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            llun_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            git_commit,
+            model: model.to_string(),
+            provider: provider.map(String::from),
+            rules: rules
+                .iter()
+                .map(|rule| ReportedRule {
+                    rule_code: rule.rule_code.clone(),
+                    hash: rule.content_hash(),
+                })
+                .collect(),
+            files: files
+                .iter()
+                .map(|file| ReportedFile {
+                    name: file.name.clone(),
+                    hash: file.content_hash(),
+                })
+                .collect(),
+            response,
+        }
+    }
+
+    /// load a report previously saved from the json output format
+    pub fn from_file(path: &Path) -> Result<Self, ResponseError> {
+        let name = path.display().to_string();
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| ResponseError::IoError(name.clone(), e))?;
+        serde_json::from_str(&contents).map_err(|e| ResponseError::JsonParseError(name, e))
+    }
+
+    /// fold another checks results into this one, i.e. each config group in a monorepo
+    /// groups can pick their own model and provider, so where they differ all of them are listed
+    pub fn merge(&mut self, other: Report) {
+        self.response
+            .detected_issues
+            .extend(other.response.detected_issues);
+        self.response.add_usage(other.response.usage);
+        self.files.extend(other.files);
+        for rule in other.rules {
+            if !self.rules.contains(&rule) {
+                self.rules.push(rule);
+            }
+        }
+        self.model = Self::merge_names(&self.model, &other.model);
+        self.provider = match (self.provider.take(), other.provider) {
+            (Some(provider), Some(other_provider)) => {
+                Some(Self::merge_names(&provider, &other_provider))
+            }
+            (provider, other_provider) => provider.or(other_provider),
+        };
+        self.generated_at = other.generated_at;
+    }
+
+    /// the most severe issue found, if any were
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.response.max_severity()
+    }
+
+    /// whether anything found is at least as severe as the given threshold
+    pub fn fails_on(&self, threshold: Severity) -> bool {
+        self.max_severity() >= Some(threshold)
+    }
+
+    /// a comma separated list of each distinct name, in the order they were first seen
+    fn merge_names(names: &str, other: &str) -> String {
+        if names.is_empty() {
+            return other.to_string();
+        }
+        if names.split(", ").any(|name| name == other) {
+            return names.to_string();
+        }
+
+        format!("{}, {}", names, other)
+    }
+}
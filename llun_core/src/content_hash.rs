@@ -0,0 +1,6 @@
+use sha2::{Digest, Sha256};
+
+/// sha256 of some content as hex, enough to tell whether a file or rule has changed between runs
+pub fn content_hash(content: impl AsRef<[u8]>) -> String {
+    format!("{:x}", Sha256::digest(content.as_ref()))
+}
@@ -0,0 +1,98 @@
+use figment::{
+    Error, Metadata, Profile, Provider,
+    value::{Dict, Map, Value},
+};
+
+/// prefix shared by every environment variable llun reads config from
+pub const ENV_PREFIX: &str = "LLUN_";
+
+/// keys whose values are lists, given in the environment as i.e. 'LLUN_SELECT=SOLID,CLASSIC'
+const LIST_KEYS: &[&str] = &[
+    "path",
+    "exclude",
+    "select",
+    "extend_select",
+    "ignore",
+    "output_format",
+];
+
+/// per-file ignores already use commas to list rules, so entries are split on ';' instead
+/// i.e. 'LLUN_PER_FILE_IGNORES=main.py:SOLID01,SOLID02;lib.py:CLASSIC01'
+const SEMICOLON_LIST_KEYS: &[&str] = &["per_file_ignores"];
+
+/// figment provider for 'LLUN_*' environment variables
+/// figments own Env provider cant read comma separated lists, hence rolling our own
+#[derive(Debug, Clone, Default)]
+pub struct LlunEnv {
+    vars: Vec<(String, String)>,
+}
+
+impl LlunEnv {
+    /// read the llun variables from the process environment
+    pub fn new() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// read the llun variables from an arbitrary set of key / value pairs
+    pub fn from_vars<I>(vars: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let vars = vars
+            .into_iter()
+            .filter_map(|(key, value)| {
+                key.strip_prefix(ENV_PREFIX)
+                    .filter(|key| !key.is_empty())
+                    .map(|key| (key.to_lowercase(), value))
+            })
+            .collect();
+
+        Self { vars }
+    }
+
+    /// turn a raw environment string into a config value based on what its key expects
+    fn parse_value(key: &str, raw: &str) -> Value {
+        let separator = if LIST_KEYS.contains(&key) {
+            Some(',')
+        } else if SEMICOLON_LIST_KEYS.contains(&key) {
+            Some(';')
+        } else {
+            None
+        };
+
+        match separator {
+            // an explicit '[...]' array is still honoured for anyone used to figment
+            Some(_) if raw.trim_start().starts_with('[') => Self::parse_figment_value(raw),
+            Some(separator) => Value::from(
+                raw.split(separator)
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<String>>(),
+            ),
+            None => Self::parse_figment_value(raw),
+        }
+    }
+
+    /// bools, numbers, strings and arrays, parsed the same way figments Env provider would
+    fn parse_figment_value(raw: &str) -> Value {
+        let Ok(value) = raw.parse::<Value>();
+        value
+    }
+}
+
+impl Provider for LlunEnv {
+    fn metadata(&self) -> Metadata {
+        Metadata::named(format!("{}* environment variable(s)", ENV_PREFIX))
+    }
+
+    fn data(&self) -> Result<Map<Profile, Dict>, Error> {
+        let dict: Dict = self
+            .vars
+            .iter()
+            .map(|(key, raw)| (key.clone(), Self::parse_value(key, raw)))
+            .collect();
+
+        Ok(Profile::Default.collect(dict))
+    }
+}
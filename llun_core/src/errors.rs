@@ -1,34 +1,176 @@
-use crate::api_client::{OpenAiClientError, PromptManagerError, ScannerError, ScannerManagerError};
-use crate::files::{FileError, FileManagerError, FileSetError};
+use std::error::Error;
+use std::fmt;
+
+use crate::api_client::{
+    OpenAiClientError, PromptManagerError, ResponseError, ScannerError, ScannerManagerError,
+};
+use crate::baseline::BaselineError;
+use crate::check::CheckError;
+use crate::files::{FileError, FileManagerError, FileSetError, GitIndexError};
 use crate::formatters::{OutputFormatterError, OutputManagerError};
 use crate::rules::{RuleError, RuleManagerError};
 use crate::per_file_ignorer::PerFileIgnorerError;
 
 /// all possible custom errors from the llun library
+/// each is transparent, so the message (and the chain under it) is the underlying errors own
 #[derive(Debug, thiserror::Error)]
 pub enum LlunCoreError {
-    #[error("Error in OpenAiClient")]
+    #[error(transparent)]
     OpenAiClientError(#[from] OpenAiClientError),
-    #[error("Error in PromptManager")]
+    #[error(transparent)]
     PromptManagerError(#[from] PromptManagerError),
-    #[error("Error in Scanner")]
+    #[error(transparent)]
     ScannerError(#[from] ScannerError),
-    #[error("Error in ScannerManager")]
+    #[error(transparent)]
     ScannerManagerError(#[from] ScannerManagerError),
-    #[error("Error in File")]
+    #[error(transparent)]
+    ResponseError(#[from] ResponseError),
+    #[error(transparent)]
     FileError(#[from] FileError),
-    #[error("Error in FileSet")]
+    #[error(transparent)]
     FileSetError(#[from] FileSetError),
-    #[error("Error in FileManager")]
+    #[error(transparent)]
     FileManagerError(#[from] FileManagerError),
-    #[error("Error in OutputFormatter")]
+    #[error(transparent)]
+    GitIndexError(#[from] GitIndexError),
+    #[error(transparent)]
     OutputFormatterError(#[from] OutputFormatterError),
-    #[error("Error in OutputManager")]
+    #[error(transparent)]
     OutputManagerError(#[from] OutputManagerError),
-    #[error("Error in Rule")]
+    #[error(transparent)]
     RuleError(#[from] RuleError),
-    #[error("Error in RuleManager")]
+    #[error(transparent)]
     RuleManagerError(#[from] RuleManagerError),
-    #[error("Error in PerFileIgnorer")]
-    PerFileIgnorerError(#[from] PerFileIgnorerError)
+    #[error(transparent)]
+    PerFileIgnorerError(#[from] PerFileIgnorerError),
+    #[error(transparent)]
+    BaselineError(#[from] BaselineError),
+    #[error(transparent)]
+    CheckError(#[from] CheckError),
+}
+
+impl LlunCoreError {
+    /// how to fix the error, from the most specific cause that knows
+    pub fn hint(&self) -> Option<String> {
+        ErrorReport::new(self).hint()
+    }
+
+    /// the wrapped error, which being transparent we'd otherwise never see in the chain
+    fn inner(&self) -> &(dyn Error + 'static) {
+        match self {
+            LlunCoreError::OpenAiClientError(error) => error,
+            LlunCoreError::PromptManagerError(error) => error,
+            LlunCoreError::ScannerError(error) => error,
+            LlunCoreError::ScannerManagerError(error) => error,
+            LlunCoreError::ResponseError(error) => error,
+            LlunCoreError::FileError(error) => error,
+            LlunCoreError::FileSetError(error) => error,
+            LlunCoreError::FileManagerError(error) => error,
+            LlunCoreError::GitIndexError(error) => error,
+            LlunCoreError::OutputFormatterError(error) => error,
+            LlunCoreError::OutputManagerError(error) => error,
+            LlunCoreError::RuleError(error) => error,
+            LlunCoreError::RuleManagerError(error) => error,
+            LlunCoreError::PerFileIgnorerError(error) => error,
+            LlunCoreError::BaselineError(error) => error,
+            LlunCoreError::CheckError(error) => error,
+        }
+    }
+}
+
+/// an error along with everything that caused it, and a hint on fixing it if llun has one
+/// '{}' gives the whole chain on one line, '{:#}' puts each cause and the hint on their own lines
+pub struct ErrorReport<'a> {
+    error: &'a (dyn Error + 'static),
+}
+
+impl<'a> ErrorReport<'a> {
+    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
+        Self { error }
+    }
+
+    pub fn error(&self) -> &'a (dyn Error + 'static) {
+        self.error
+    }
+
+    /// whatever caused the error, outermost first
+    /// plenty of errors (ours included, once) repeat their source in their own message, those causes are skipped
+    pub fn causes(&self) -> Vec<&'a (dyn Error + 'static)> {
+        let mut causes = Vec::new();
+        let mut shown = self.error.to_string();
+        let mut source = self.error.source();
+        while let Some(cause) = source {
+            let message = cause.to_string();
+            if !shown.contains(&message) {
+                causes.push(cause);
+                shown = message;
+            }
+            source = cause.source();
+        }
+
+        causes
+    }
+
+    /// the hint from the deepest error in the chain that has one, as thats the closest to what went wrong
+    pub fn hint(&self) -> Option<String> {
+        std::iter::successors(Some(self.error), |&error| error.source())
+            .filter_map(error_hint)
+            .last()
+    }
+}
+
+impl fmt::Display for ErrorReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if f.alternate() {
+            for cause in self.causes() {
+                write!(f, "\n  caused by: {}", cause)?;
+            }
+            if let Some(hint) = self.hint() {
+                write!(f, "\n  hint: {}", hint)?;
+            }
+        } else {
+            for cause in self.causes() {
+                write!(f, ": {}", cause)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// the hint for a single error, if its one of ours that has any
+fn error_hint(error: &(dyn Error + 'static)) -> Option<String> {
+    if let Some(error) = error.downcast_ref::<LlunCoreError>() {
+        return error_hint(error.inner());
+    }
+    if let Some(error) = error.downcast_ref::<CheckError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<ScannerManagerError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<ScannerError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<OpenAiClientError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<ResponseError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<FileError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<FileManagerError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<GitIndexError>() {
+        return error.hint();
+    }
+    if let Some(error) = error.downcast_ref::<RuleManagerError>() {
+        return error.hint();
+    }
+
+    None
 }
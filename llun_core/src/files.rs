@@ -1,7 +1,9 @@
 pub mod file;
 pub mod file_manager;
 pub mod file_set;
+pub mod git_index;
 
-pub use file::{File, FileError};
-pub use file_manager::{FileManager, FileManagerError};
+pub use file::{File, FileError, normalise_path};
+pub use file_manager::{FileManager, FileManagerError, STDIN_PATH};
 pub use file_set::{FileSet, FileSetError};
+pub use git_index::{GitIndex, GitIndexError};
@@ -1,11 +1,36 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fs;
+use std::io::{self, Read};
+
+use crate::content_hash::content_hash;
 
 #[derive(Debug, thiserror::Error)]
 pub enum FileError {
-    #[error("Rule failed to be read file")]
-    FileReadError(#[from] std::io::Error),
+    #[error("Failed to read {0}")]
+    FileReadError(String, #[source] io::Error),
+}
+
+impl FileError {
+    pub fn hint(&self) -> Option<String> {
+        let FileError::FileReadError(name, e) = self;
+        match e.kind() {
+            io::ErrorKind::InvalidData => Some(format!(
+                "llun only reads utf-8 text, skip '{}' with --exclude (or 'exclude' in llun.toml)",
+                name
+            )),
+            io::ErrorKind::PermissionDenied => Some(format!("check you have permission to read '{}'", name)),
+            io::ErrorKind::NotFound => {
+                Some("check the path exists, paths are relative to where llun is run".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// a file path as llun compares them, the model (and users) arent always consistent about leading './'s
+pub fn normalise_path(path: &str) -> &str {
+    path.trim_start_matches("./")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,13 +43,28 @@ pub struct File {
 impl File {
     /// load a file from a given path
     pub fn from_file(file_path: String) -> Result<Self, FileError> {
-        let content = fs::read_to_string(&file_path)?;
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| FileError::FileReadError(file_path.clone(), e))?;
 
         Ok(File {
             name: file_path,
             content,
         })
     }
+
+    /// load a file from anything readable (i.e. stdin), under the name it should be reported as
+    pub fn from_reader(name: String, mut reader: impl Read) -> Result<Self, FileError> {
+        let mut content = String::new();
+        reader
+            .read_to_string(&mut content)
+            .map_err(|e| FileError::FileReadError(name.clone(), e))?;
+
+        Ok(File { name, content })
+    }
+
+    pub fn content_hash(&self) -> String {
+        content_hash(&self.content)
+    }
 }
 
 impl fmt::Display for File {
@@ -1,26 +1,43 @@
 use ignore::WalkBuilder;
 use std::collections::HashSet;
-use std::io;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use tracing::debug;
 
-use crate::files::{File, FileError, FileSet};
+use crate::files::{File, FileError, FileSet, GitIndex, GitIndexError};
+use crate::result_cache::CACHE_DIR;
 
 // claude suggested these custom errors
 #[derive(Debug, thiserror::Error)]
 pub enum FileManagerError {
     #[error("Path doesn't exist: {0}")]
     PathNotFound(String),
-    #[error("Failed to load file: {0}")]
-    FileSetLoadError(String),
-    #[error("IO error: {0}")]
+    #[error("Failed to read the list of paths")]
     IoError(#[from] io::Error),
-    #[error("walk error: {0}")]
+    #[error("Failed to walk the selected paths")]
     WalkError(#[from] ignore::Error),
-    #[error("Rule failed to be read file")]
+    #[error(transparent)]
     FileReadError(#[from] FileError),
+    #[error("Failed to read from the git index")]
+    GitIndexError(#[from] GitIndexError),
 }
 
+impl FileManagerError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            FileManagerError::PathNotFound(_) => {
+                Some("check the path exists, paths are relative to where llun is run".to_string())
+            }
+            // transparent, so its never seen on its own when walking the chain
+            FileManagerError::FileReadError(e) => e.hint(),
+            _ => None,
+        }
+    }
+}
+
+/// the path users give to mean 'read this from stdin instead'
+pub const STDIN_PATH: &str = "-";
+
 /// The cli / toml values that a user can use to control files
 #[derive(Debug, Default, Clone)]
 pub struct FileSelectionConfig {
@@ -35,6 +52,13 @@ pub struct FileManager {}
 impl FileManager {
     /// load the files into a FileSet based on the users provided config
     pub fn load_fileset(config: &FileSelectionConfig) -> Result<FileSet, FileManagerError> {
+        let all_files = Self::collect_paths(config)?;
+
+        FileManager::load_from_files(all_files)
+    }
+
+    /// get every filepath selected by the users config, without reading them
+    pub fn collect_paths(config: &FileSelectionConfig) -> Result<Vec<PathBuf>, FileManagerError> {
         let mut all_files = Vec::new();
         let exclude_set: HashSet<PathBuf> = config.exclude.iter().cloned().collect();
 
@@ -44,8 +68,7 @@ impl FileManager {
             all_files.extend(files);
         }
 
-        FileManager::load_from_files(all_files)
-            .map_err(|e| FileManagerError::FileSetLoadError(e.to_string()))
+        Ok(all_files)
     }
 
     /// create a fileset
@@ -53,15 +76,52 @@ impl FileManager {
         let mut collection = FileSet::new();
 
         for file_path in file_paths {
-            match File::from_file(file_path.to_string_lossy().to_string()) {
-                Ok(file) => collection.add_file(file),
-                Err(e) => return Err(FileManagerError::FileReadError(e)),
-            }
+            collection.add_file(File::from_file(file_path.to_string_lossy().to_string())?);
         }
         debug!("Loaded files: {}", &collection);
         Ok(collection)
     }
 
+    /// create a fileset from the staged content of the given files, rather than the working tree
+    pub fn load_from_index(
+        file_paths: Vec<PathBuf>,
+        index: &GitIndex,
+    ) -> Result<FileSet, FileManagerError> {
+        let mut collection = FileSet::new();
+
+        for file_path in file_paths {
+            collection.add_file(index.read_file(&file_path)?);
+        }
+        debug!("Loaded staged files: {}", &collection);
+        Ok(collection)
+    }
+
+    /// read a list of paths from a file, or stdin if given '-'
+    pub fn read_path_list(source: &Path) -> Result<Vec<PathBuf>, FileManagerError> {
+        let mut contents = String::new();
+        if source == Path::new(STDIN_PATH) {
+            io::stdin().read_to_string(&mut contents)?;
+        } else {
+            Self::validate_path(source)?;
+            contents = std::fs::read_to_string(source)?;
+        }
+
+        Ok(Self::parse_path_list(&contents))
+    }
+
+    /// split a newline or NUL separated list of paths, i.e. from 'git diff --name-only -z'
+    /// NUL wins if present, as thats the only safe way to list paths containing newlines
+    pub fn parse_path_list(contents: &str) -> Vec<PathBuf> {
+        let separator = if contents.contains('\0') { '\0' } else { '\n' };
+
+        contents
+            .split(separator)
+            .map(|path| path.trim_end_matches('\r'))
+            .filter(|path| !path.trim().is_empty())
+            .map(PathBuf::from)
+            .collect()
+    }
+
     /// validate that the provided path exists
     pub fn validate_path(path: &Path) -> Result<(), FileManagerError> {
         if !path.exists() {
@@ -85,6 +145,8 @@ impl FileManager {
         builder.git_ignore(!no_respect_gitignore);
         builder.hidden(false);
         builder.follow_links(false);
+        // the cache ignores itself for git, but it should never be checked even outside of git
+        builder.filter_entry(|entry| entry.file_name() != CACHE_DIR);
 
         let walker = builder.build();
 
@@ -118,4 +180,19 @@ impl FileManager {
 
         Self::load_fileset(&config)
     }
+
+    /// CLI facing entry point for when the paths are needed before the content
+    pub fn collect_from_cli(
+        paths: Vec<PathBuf>,
+        exclude: Vec<PathBuf>,
+        no_respect_gitignore: bool,
+    ) -> Result<Vec<PathBuf>, FileManagerError> {
+        let config = FileSelectionConfig {
+            paths,
+            exclude,
+            no_respect_gitignore,
+        };
+
+        Self::collect_paths(&config)
+    }
 }
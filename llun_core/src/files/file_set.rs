@@ -3,7 +3,7 @@ use std::fmt;
 
 #[derive(Debug, thiserror::Error)]
 pub enum FileSetError {
-    #[error("Rule failed to be read file")]
+    #[error(transparent)]
     FileReadError(#[from] FileError),
 }
 
@@ -21,6 +21,19 @@ impl FileSet {
     pub fn add_file(&mut self, file: File) {
         self.files.push(file);
     }
+
+    /// borrowed iteration over the files
+    pub fn iter(&self) -> std::slice::Iter<'_, File> {
+        self.files.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
 }
 
 /// we will (hopefully) use display to insert into a markdown message?
@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+use crate::files::File;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GitIndexError {
+    #[error("Failed to run git, is it installed?")]
+    GitNotFound(#[from] std::io::Error),
+    #[error("git {0} failed: {1}")]
+    GitCommandFailed(String, String),
+    #[error("Staged content isnt valid UTF-8: {0}")]
+    InvalidUtf8(String),
+    #[error("File is not staged: {0}")]
+    NotStaged(String),
+}
+
+impl GitIndexError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            GitIndexError::GitNotFound(_) => Some("install git, or check without --staged".to_string()),
+            GitIndexError::NotStaged(path) => {
+                Some(format!("stage it with 'git add {}', or check without --staged", path))
+            }
+            GitIndexError::GitCommandFailed(_, stderr) if stderr.contains("not a git repository") => {
+                Some("--staged only works inside a git repository".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// the files staged for the next commit, read from the git index rather than the working tree
+/// so that a pre-commit check sees exactly what will be committed
+#[derive(Debug, Clone)]
+pub struct GitIndex {
+    root: PathBuf,
+    staged: HashSet<PathBuf>,
+}
+
+impl GitIndex {
+    /// read the staged file list from the repository containing the working directory
+    pub fn open() -> Result<Self, GitIndexError> {
+        Self::open_in(Path::new("."))
+    }
+
+    /// read the staged file list from the repository containing the given directory
+    pub fn open_in(dir: &Path) -> Result<Self, GitIndexError> {
+        let root = Self::canonical(Path::new(
+            Self::git(dir, &["rev-parse", "--show-toplevel"])?.trim(),
+        ));
+
+        // added, copied, modified or renamed - deleted files have nothing to review
+        let staged = Self::git(&root, &[
+            "diff",
+            "--cached",
+            "--name-only",
+            "--diff-filter=ACMR",
+            "-z",
+        ])?
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(|path| Self::canonical(&root.join(path)))
+        .collect();
+        debug!("Staged files: {:?}", &staged);
+
+        Ok(Self { root, staged })
+    }
+
+    /// whether a path (as selected by the user) is staged
+    pub fn is_staged(&self, path: &Path) -> bool {
+        self.staged.contains(&Self::canonical(path))
+    }
+
+    /// load the staged version of a file, keeping the path as given for reporting
+    pub fn read_file(&self, path: &Path) -> Result<File, GitIndexError> {
+        let canonical_path = Self::canonical(path);
+        if !self.staged.contains(&canonical_path) {
+            return Err(GitIndexError::NotStaged(path.to_string_lossy().to_string()));
+        }
+
+        let relative_path = canonical_path
+            .strip_prefix(&self.root)
+            .unwrap_or(&canonical_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let content = Self::git(&self.root, &["show", &format!(":{}", relative_path)])?;
+
+        Ok(File {
+            name: path.to_string_lossy().to_string(),
+            content,
+        })
+    }
+
+    /// the commit checked out in the given directory, if its in a git repository at all
+    pub fn head_commit(dir: &Path) -> Option<String> {
+        Self::git(dir, &["rev-parse", "HEAD"])
+            .ok()
+            .map(|commit| commit.trim().to_string())
+    }
+
+    /// run a git command from the given directory and hand back its stdout
+    fn git(dir: &Path, args: &[&str]) -> Result<String, GitIndexError> {
+        let output = Command::new("git").args(args).current_dir(dir).output()?;
+        if !output.status.success() {
+            return Err(GitIndexError::GitCommandFailed(
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        String::from_utf8(output.stdout).map_err(|_| GitIndexError::InvalidUtf8(args.join(" ")))
+    }
+
+    /// git reports paths from the repo root, users give them from wherever - so compare canonically
+    fn canonical(path: &Path) -> PathBuf {
+        path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+    }
+}
@@ -1,15 +1,15 @@
 pub mod azure_formatter;
 pub mod json_formatter;
 pub mod junit_formatter;
-pub mod output_format;
 pub mod output_formatter;
 pub mod output_manager;
 pub mod summary_formatter;
+pub mod template_formatter;
 
 pub use azure_formatter::AzureFormatter;
 pub use json_formatter::JsonFormatter;
 pub use junit_formatter::JunitFormatter;
-pub use output_format::OutputFormat;
 pub use output_formatter::{OutputFormatter, OutputFormatterError};
 pub use output_manager::{OutputManager, OutputManagerError};
 pub use summary_formatter::SummaryFormatter;
+pub use template_formatter::TemplateFormatter;
@@ -1,11 +1,25 @@
 use crate::api_client::Response;
+use crate::check::Report;
 use crate::formatters::{OutputFormatter, OutputFormatterError};
 
-pub struct JsonFormatter;
+/// a check is written as its whole report, metadata and all, unless 'legacy' asks for the issues alone
+/// a bare response (i.e. legacy results through 'llun report') has no metadata to give, so is always written as is
+#[derive(Default)]
+pub struct JsonFormatter {
+    pub legacy: bool,
+}
 
 /// make use of the output formatter abstraction
 impl OutputFormatter for JsonFormatter {
     fn format(&self, response: &Response) -> Result<String, OutputFormatterError> {
         Ok(serde_json::to_string_pretty(response)?)
     }
+
+    fn format_report(&self, report: &Report) -> Result<String, OutputFormatterError> {
+        if self.legacy {
+            return self.format(&report.response);
+        }
+
+        Ok(serde_json::to_string_pretty(report)?)
+    }
 }
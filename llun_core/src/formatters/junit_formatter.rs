@@ -1,12 +1,9 @@
-use std::fs::File;
-
 use junit_report::{Duration, Report, TestCase, TestSuite};
 
 use crate::api_client::Response;
 use crate::formatters::{OutputFormatter, OutputFormatterError};
 
-const OUTPUT_PATH: &str = "llun-results.xml";
-
+/// the results as a junit xml report, writing it somewhere is left to the caller
 pub struct JunitFormatter;
 
 /// make use of the output formatter abstraction
@@ -41,10 +38,12 @@ impl OutputFormatter for JunitFormatter {
 
         report.add_testsuite(test_suite);
 
-        let mut file = File::create(OUTPUT_PATH)
-            .map_err(|e| OutputFormatterError::IoError(format!("Failed to create file: {}", e)))?;
-        report.write_xml(&mut file).unwrap();
+        let mut xml = Vec::new();
+        report
+            .write_xml(&mut xml)
+            .map_err(|e| OutputFormatterError::FormatError(format!("Failed to write the junit xml: {}", e)))?;
 
-        Ok(format!("JUnit report created at: {}", OUTPUT_PATH))
+        String::from_utf8(xml)
+            .map_err(|e| OutputFormatterError::FormatError(format!("Junit xml wasnt valid utf-8: {}", e)))
     }
 }
@@ -1,17 +1,25 @@
 use crate::api_client::Response;
+use crate::check::Report;
 
 #[derive(Debug, thiserror::Error)]
 pub enum OutputFormatterError {
-    #[error("Failed to load models response to JSON {0}")]
+    #[error("Failed to load models response to JSON")]
     ModelResponseNotLoadable(#[from] serde_json::Error),
     #[error("Failed to produce the relevant format {0}")]
     FormatError(String),
     #[error("I/O wasnt successful {0}")]
     IoError(String),
+    #[error("Failed to render the template: {0:#}")]
+    TemplateError(#[from] minijinja::Error),
 }
 
-pub trait OutputFormatter {
+pub trait OutputFormatter: Send + Sync {
     /// anything which can format is a formatter
     /// does this belong elsewhere? not sure on the organisation atm...
     fn format(&self, response: &Response) -> Result<String, OutputFormatterError>;
+
+    /// the full report of a check, most formats only care about the issues in it
+    fn format_report(&self, report: &Report) -> Result<String, OutputFormatterError> {
+        self.format(&report.response)
+    }
 }
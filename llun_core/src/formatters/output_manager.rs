@@ -1,22 +1,30 @@
 use crate::api_client::Response;
+use crate::check::Report;
 use crate::formatters::{
-    AzureFormatter, JsonFormatter, JunitFormatter, OutputFormat, OutputFormatter,
-    OutputFormatterError, SummaryFormatter,
+    AzureFormatter, JsonFormatter, JunitFormatter, OutputFormatter, OutputFormatterError,
+    SummaryFormatter, TemplateFormatter,
 };
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tracing::debug;
+
+/// id of the formatter that renders a user supplied template
+pub const TEMPLATE_FORMAT: &str = "template";
 
 #[derive(Debug, thiserror::Error)]
 pub enum OutputManagerError {
-    #[error("Failed to format the model output using the desired method: {0}")]
+    #[error("Failed to format the model output using the desired method")]
     OutputFormattingFailed(#[from] OutputFormatterError),
+    #[error("Unknown output format '{0}', expected one of: {1}")]
+    FormatterNotFound(String, String),
 }
 
+/// every available formatter, keyed by the id users select it with i.e. 'json'
+/// embedders can register their own alongside (or over) llun's
 pub struct OutputManager {
-    formatters: HashMap<OutputFormat, Box<dyn OutputFormatter>>,
+    formatters: BTreeMap<String, Box<dyn OutputFormatter>>,
 }
 
-/// manager the output format of the object in a cool, scalable way
-/// is there no option for dynamic registry in rust?
 impl Default for OutputManager {
     fn default() -> Self {
         Self::new()
@@ -24,32 +32,119 @@ impl Default for OutputManager {
 }
 
 impl OutputManager {
-    /// register all formatters to the object
+    /// register all built-in formatters to the object
+    /// the template formatter has nothing to render until 'with_template' is given one
     pub fn new() -> Self {
-        let mut formatters: HashMap<OutputFormat, Box<dyn OutputFormatter>> = HashMap::new();
+        let mut output_manager = Self::empty();
+
+        output_manager.register("json", Box::new(JsonFormatter::default()));
+        output_manager.register("azure", Box::new(AzureFormatter));
+        output_manager.register("junit", Box::new(JunitFormatter));
+        output_manager.register("summary", Box::new(SummaryFormatter));
+        output_manager.register(TEMPLATE_FORMAT, Box::new(TemplateFormatter::default()));
+
+        output_manager
+    }
 
-        formatters.insert(OutputFormat::Json, Box::new(JsonFormatter));
-        formatters.insert(OutputFormat::Azure, Box::new(AzureFormatter));
-        formatters.insert(OutputFormat::Junit, Box::new(JunitFormatter));
-        formatters.insert(OutputFormat::Summary, Box::new(SummaryFormatter));
+    /// no formatters at all, for embedders that only want their own
+    pub fn empty() -> Self {
+        Self {
+            formatters: BTreeMap::new(),
+        }
+    }
+
+    /// render the 'template' format with the given template file, if there is one
+    pub fn with_template(mut self, template: Option<&Path>) -> Result<Self, OutputManagerError> {
+        if let Some(template) = template {
+            self.register(TEMPLATE_FORMAT, Box::new(TemplateFormatter::from_file(template)?));
+        }
 
-        Self { formatters }
+        Ok(self)
+    }
+
+    /// write 'json' as the bare list of issues, the shape it had before reports carried metadata
+    pub fn with_legacy_json(mut self, legacy: bool) -> Self {
+        if legacy {
+            self.register("json", Box::new(JsonFormatter { legacy }));
+        }
+
+        self
+    }
+
+    /// add a formatter, replacing whatever was registered under its id
+    pub fn register(&mut self, id: impl Into<String>, formatter: Box<dyn OutputFormatter>) {
+        let id = id.into();
+        debug!("Registering formatter '{}'", id);
+        self.formatters.insert(id, formatter);
+    }
+
+    /// the id of every registered formatter, in order
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.formatters.keys().map(String::as_str)
     }
 
     /// use the selected formats in order
     pub fn process_response(
         &self,
         response: &Response,
-        output_formats: &[OutputFormat],
+        output_formats: &[String],
+    ) -> Result<(), OutputManagerError> {
+        output_formats
+            .iter()
+            .try_for_each(|output_format| -> Result<(), OutputManagerError> {
+                println!("{}", self.format_response(response, output_format)?);
+                Ok(())
+            })
+    }
+
+    /// the response in a single format, for callers that want the text rather than it printed
+    pub fn format_response(
+        &self,
+        response: &Response,
+        output_format: &str,
+    ) -> Result<String, OutputManagerError> {
+        Ok(self.formatter(output_format)?.format(response)?)
+    }
+
+    /// use the selected formats in order, on a checks full report
+    pub fn process_report(
+        &self,
+        report: &Report,
+        output_formats: &[String],
     ) -> Result<(), OutputManagerError> {
         output_formats
             .iter()
-            .filter_map(|format| self.formatters.get(format))
-            .try_for_each(|formatter| -> Result<(), OutputManagerError> {
-                println!("{}", formatter.format(response)?);
+            .try_for_each(|output_format| -> Result<(), OutputManagerError> {
+                println!("{}", self.format_report(report, output_format)?);
                 Ok(())
-            })?;
+            })
+    }
 
-        Ok(())
+    /// a checks full report in a single format
+    pub fn format_report(
+        &self,
+        report: &Report,
+        output_format: &str,
+    ) -> Result<String, OutputManagerError> {
+        Ok(self.formatter(output_format)?.format_report(report)?)
     }
+
+    fn formatter(&self, output_format: &str) -> Result<&dyn OutputFormatter, OutputManagerError> {
+        self.formatters
+            .get(output_format)
+            .map(|formatter| formatter.as_ref())
+            .ok_or_else(|| {
+                OutputManagerError::FormatterNotFound(
+                    output_format.to_string(),
+                    self.ids().collect::<Vec<_>>().join(", "),
+                )
+            })
+    }
+}
+
+/// every built-in format id as a clap value, so typos are caught (and completed) before anything runs
+#[cfg(feature = "clap")]
+pub fn output_format_value_parser() -> clap::builder::PossibleValuesParser {
+    let ids: Vec<String> = OutputManager::new().ids().map(String::from).collect();
+    clap::builder::PossibleValuesParser::new(ids)
 }
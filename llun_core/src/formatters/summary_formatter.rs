@@ -9,7 +9,11 @@ pub struct SummaryFormatter;
 impl OutputFormatter for SummaryFormatter {
     fn format(&self, response: &Response) -> Result<String, OutputFormatterError> {
         if response.detected_issues.is_empty() {
-            return Ok("\x1b[32m✓ No issues detected\x1b[0m".to_string());
+            let mut output = "\x1b[32m✓ No issues detected\x1b[0m".to_string();
+            if let Some(usage) = &response.usage {
+                output.push_str(&format!("\n\x1b[90m{}\x1b[0m", usage));
+            }
+            return Ok(output);
         }
 
         let mut output = String::new();
@@ -68,6 +72,9 @@ impl OutputFormatter for SummaryFormatter {
         for (file_path, issues) in file_list {
             output.push_str(&format!("  * {} with {} issues\n", file_path, issues.len()));
         }
+        if let Some(usage) = &response.usage {
+            output.push_str(&format!("\n\x1b[90m{}\x1b[0m\n", usage));
+        }
 
         output.push_str("\n\x1b[1m=========================================\x1b[0m\n");
 
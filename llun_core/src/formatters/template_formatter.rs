@@ -0,0 +1,63 @@
+use minijinja::{Environment, context};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::api_client::Response;
+use crate::formatters::{OutputFormatter, OutputFormatterError};
+
+/// renders the response with a user supplied minijinja template, i.e. a slack message or ticket body
+/// templates see 'detected_issues', 'files' (each file with an issue) and 'max_severity'
+#[derive(Debug, Default)]
+pub struct TemplateFormatter {
+    /// None until a template is given, so the format can be listed before anyone picks it
+    source: Option<String>,
+    /// shown in render errors, the file path when read from one
+    name: String,
+}
+
+impl TemplateFormatter {
+    pub fn new(name: impl Into<String>, source: impl Into<String>) -> Result<Self, OutputFormatterError> {
+        let name = name.into();
+        let source = source.into();
+        // parse now, so a broken template fails before the scan rather than after paying for it
+        Environment::new().template_from_named_str(&name, &source)?;
+
+        Ok(Self {
+            source: Some(source),
+            name,
+        })
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, OutputFormatterError> {
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            OutputFormatterError::IoError(format!("Failed to read template {}: {}", path.display(), e))
+        })?;
+
+        Self::new(path.display().to_string(), source)
+    }
+}
+
+impl OutputFormatter for TemplateFormatter {
+    fn format(&self, response: &Response) -> Result<String, OutputFormatterError> {
+        let source = self.source.as_deref().ok_or_else(|| {
+            OutputFormatterError::FormatError(
+                "no template given, set 'template' or pass --template".to_string(),
+            )
+        })?;
+
+        let mut environment = Environment::new();
+        environment.add_template(&self.name, source)?;
+
+        let files: BTreeSet<&str> = response
+            .detected_issues
+            .iter()
+            .map(|issue| issue.file_path.as_str())
+            .collect();
+
+        Ok(environment.get_template(&self.name)?.render(context! {
+            detected_issues => response.detected_issues,
+            files => files,
+            max_severity => response.max_severity(),
+        })?)
+    }
+}
@@ -1,17 +1,29 @@
 pub mod api_client;
+pub mod check;
 pub mod data;
 pub mod files;
 pub mod formatters;
 pub mod rules;
 pub mod per_file_ignorer;
+pub mod baseline;
+pub mod result_cache;
 pub mod errors;
 pub mod append_to_file;
+pub mod env_provider;
+pub mod content_hash;
+#[cfg(feature = "python")]
+pub mod python;
 
-pub use api_client::{AvailableScanner, PromptManager, ScannerManager};
+pub use api_client::{PromptManager, ScannerFactory, ScannerManager};
+pub use check::{CheckConfig, Llun, LlunBuilder, Report};
 pub use data::DEFAULT_CONFIG;
 pub use files::FileManager;
-pub use formatters::{OutputFormat, OutputManager};
+pub use formatters::{OutputFormatter, OutputManager};
 pub use rules::RuleManager;
 pub use per_file_ignorer::PerFileIgnorer;
-pub use errors::LlunCoreError;
-pub use append_to_file::append_to_file;
\ No newline at end of file
+pub use baseline::Baseline;
+pub use result_cache::ResultCache;
+pub use errors::{ErrorReport, LlunCoreError};
+pub use append_to_file::append_to_file;
+pub use env_provider::LlunEnv;
+pub use content_hash::content_hash;
\ No newline at end of file
@@ -10,7 +10,7 @@ pub enum PerFileIgnorerError {
     NoPathProvided(String),
     #[error("Per-file-ignore requires at least one rule to ignore: {0}")]
     NoRulesProvided(String),
-    #[error("Failed to parse response as JSON: {0}")]
+    #[error("Failed to parse response as JSON")]
     JsonParseError(#[from] serde_json::Error),
     #[error("Failed to extract json from response")]
     JsonCleaningError,
@@ -0,0 +1,42 @@
+//! the 'llun' python module, so python tooling can run llun in-process rather than shelling out
+//! built with maturin from llun_core/pyproject.toml, only compiled with the 'python' feature
+pub mod files;
+pub mod formatters;
+pub mod response;
+pub mod rules;
+pub mod scanner;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use crate::errors::ErrorReport;
+
+pub use files::PyFileSet;
+pub use formatters::format_response;
+pub use response::{PyDetectedIssue, PyResponse};
+pub use rules::{PyRuleSet, load_rules};
+pub use scanner::PyScanner;
+
+create_exception!(llun, LlunError, PyException, "Raised whenever llun itself fails.");
+
+/// any llun error as a python exception, with each cause and the hint (if any) on their own lines
+pub(crate) fn llun_error(error: impl std::error::Error + 'static) -> PyErr {
+    LlunError::new_err(format!("{:#}", ErrorReport::new(&error)))
+}
+
+#[pymodule]
+fn llun(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("LlunError", m.py().get_type::<LlunError>())?;
+
+    m.add_class::<PyRuleSet>()?;
+    m.add_class::<PyFileSet>()?;
+    m.add_class::<PyDetectedIssue>()?;
+    m.add_class::<PyResponse>()?;
+    m.add_class::<PyScanner>()?;
+
+    m.add_function(wrap_pyfunction!(load_rules, m)?)?;
+    m.add_function(wrap_pyfunction!(format_response, m)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use pyo3::prelude::*;
+use std::path::PathBuf;
+
+use crate::files::{File, FileManager, FileSet};
+use crate::python::llun_error;
+
+/// the files a scan looks at, either read from disk or added by hand
+#[pyclass(name = "FileSet", module = "llun.llun")]
+#[derive(Default)]
+pub struct PyFileSet {
+    pub(crate) files: FileSet,
+}
+
+#[pymethods]
+impl PyFileSet {
+    /// an empty set, to 'add' files to
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// read every file under the given paths, the same way the cli selects them
+    #[staticmethod]
+    #[pyo3(signature = (paths, exclude = Vec::new(), no_respect_gitignore = false))]
+    fn from_paths(
+        paths: Vec<PathBuf>,
+        exclude: Vec<PathBuf>,
+        no_respect_gitignore: bool,
+    ) -> PyResult<Self> {
+        let files = FileManager::load_from_cli(paths, exclude, no_respect_gitignore)
+            .map_err(llun_error)?;
+
+        Ok(Self { files })
+    }
+
+    /// add a file that isnt on disk (or hasnt been saved yet), under the name it should be reported as
+    fn add(&mut self, name: String, content: String) {
+        self.files.add_file(File { name, content });
+    }
+
+    /// the name of every file in the set, in the order they were added
+    #[getter]
+    fn names(&self) -> Vec<String> {
+        self.files.iter().map(|file| file.name.clone()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.files.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("FileSet({:?})", self.names())
+    }
+}
@@ -0,0 +1,25 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::path::PathBuf;
+
+use crate::formatters::{OutputManager, OutputManagerError};
+use crate::python::llun_error;
+use crate::python::response::PyResponse;
+
+/// the response in one of the cli's output formats, i.e. 'json', 'azure', 'junit' or 'summary'
+/// the 'template' format renders the given minijinja template file
+#[pyfunction]
+#[pyo3(signature = (response, output_format = "summary", template = None))]
+pub fn format_response(
+    response: &PyResponse,
+    output_format: &str,
+    template: Option<PathBuf>,
+) -> PyResult<String> {
+    OutputManager::new()
+        .with_template(template.as_deref())
+        .and_then(|output_manager| output_manager.format_response(&response.response, output_format))
+        .map_err(|e| match e {
+            OutputManagerError::FormatterNotFound(..) => PyValueError::new_err(e.to_string()),
+            e => llun_error(e),
+        })
+}
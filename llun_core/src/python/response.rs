@@ -0,0 +1,163 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::api_client::{DetectedIssue, Response};
+use crate::python::llun_error;
+use crate::rules::Severity;
+
+/// a single problem the scanner found
+#[pyclass(name = "DetectedIssue", module = "llun.llun", frozen)]
+#[derive(Clone)]
+pub struct PyDetectedIssue {
+    pub(crate) issue: DetectedIssue,
+}
+
+#[pymethods]
+impl PyDetectedIssue {
+    /// mostly for building the response a mock scanner should give
+    #[new]
+    #[pyo3(signature = (
+        rule_code,
+        name,
+        file_path,
+        brief_description,
+        explanation,
+        suggested_alternative,
+        code_snippet,
+        severity = "warning",
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        rule_code: String,
+        name: String,
+        file_path: String,
+        brief_description: String,
+        explanation: String,
+        suggested_alternative: String,
+        code_snippet: String,
+        severity: &str,
+    ) -> PyResult<Self> {
+        let severity = severity.parse::<Severity>().map_err(PyValueError::new_err)?;
+
+        Ok(Self {
+            issue: DetectedIssue {
+                rule_code,
+                name,
+                file_path,
+                brief_description,
+                explanation,
+                suggested_alternative,
+                code_snippet,
+                severity,
+            },
+        })
+    }
+
+    #[getter]
+    fn rule_code(&self) -> &str {
+        &self.issue.rule_code
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        &self.issue.name
+    }
+
+    #[getter]
+    fn file_path(&self) -> &str {
+        &self.issue.file_path
+    }
+
+    #[getter]
+    fn brief_description(&self) -> &str {
+        &self.issue.brief_description
+    }
+
+    #[getter]
+    fn explanation(&self) -> &str {
+        &self.issue.explanation
+    }
+
+    #[getter]
+    fn suggested_alternative(&self) -> &str {
+        &self.issue.suggested_alternative
+    }
+
+    #[getter]
+    fn code_snippet(&self) -> &str {
+        &self.issue.code_snippet
+    }
+
+    /// one of 'info', 'warning' or 'error', taken from the rule once the scan is back
+    #[getter]
+    fn severity(&self) -> String {
+        self.issue.severity.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DetectedIssue(rule_code={:?}, file_path={:?}, severity={:?})",
+            self.issue.rule_code,
+            self.issue.file_path,
+            self.issue.severity.to_string()
+        )
+    }
+}
+
+/// everything a scan found
+#[pyclass(name = "Response", module = "llun.llun", frozen)]
+#[derive(Clone, Default)]
+pub struct PyResponse {
+    pub(crate) response: Response,
+}
+
+#[pymethods]
+impl PyResponse {
+    #[new]
+    #[pyo3(signature = (detected_issues = Vec::new()))]
+    fn new(detected_issues: Vec<PyDetectedIssue>) -> Self {
+        Self {
+            response: Response {
+                detected_issues: detected_issues.into_iter().map(|issue| issue.issue).collect(),
+                usage: None,
+            },
+        }
+    }
+
+    /// load a response saved with the json format
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let response = serde_json::from_str(json).map_err(llun_error)?;
+
+        Ok(Self { response })
+    }
+
+    /// the same json the cli's json format writes
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string_pretty(&self.response).map_err(llun_error)
+    }
+
+    #[getter]
+    fn detected_issues(&self) -> Vec<PyDetectedIssue> {
+        self.response
+            .detected_issues
+            .iter()
+            .cloned()
+            .map(|issue| PyDetectedIssue { issue })
+            .collect()
+    }
+
+    /// the severity of the worst issue found, or None if nothing was
+    #[getter]
+    fn max_severity(&self) -> Option<String> {
+        self.response.max_severity().map(|severity| severity.to_string())
+    }
+
+    fn __len__(&self) -> usize {
+        self.response.detected_issues.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Response({} detected issue(s))", self.response.detected_issues.len())
+    }
+}
@@ -0,0 +1,53 @@
+use pyo3::prelude::*;
+use std::sync::Arc;
+
+use crate::python::llun_error;
+use crate::rules::{RuleManager, RuleSet};
+
+/// the rules a scan checks against, from 'load_rules'
+/// shared rather than copied into scans, as the severities are applied once the scan is back
+#[pyclass(name = "RuleSet", module = "llun.llun", frozen)]
+pub struct PyRuleSet {
+    pub(crate) rules: Arc<RuleSet>,
+}
+
+#[pymethods]
+impl PyRuleSet {
+    /// code of every rule in the set, i.e. 'SOLID01'
+    #[getter]
+    fn codes(&self) -> Vec<String> {
+        self.rules.iter().map(|rule| rule.rule_code.clone()).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.rules.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RuleSet({:?})", self.codes())
+    }
+
+    /// the rules as markdown, the same as they appear in the prompt
+    fn __str__(&self) -> String {
+        self.rules.to_string()
+    }
+}
+
+/// select rules the same way the cli does, by rule code or family
+/// user-defined rules are read from the 'llun' folder in the working directory
+#[pyfunction]
+#[pyo3(signature = (select, extend_select = Vec::new(), ignore = Vec::new()))]
+pub fn load_rules(
+    select: Vec<String>,
+    extend_select: Vec<String>,
+    ignore: Vec<String>,
+) -> PyResult<PyRuleSet> {
+    let rule_manager = RuleManager::new().map_err(llun_error)?;
+    let rules = rule_manager
+        .load_from_cli(select, extend_select, ignore)
+        .map_err(llun_error)?;
+
+    Ok(PyRuleSet {
+        rules: Arc::new(rules),
+    })
+}
@@ -0,0 +1,142 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::sync::Arc;
+
+use crate::api_client::{MockScanner, PromptManager, ScannerManager};
+use crate::python::files::PyFileSet;
+use crate::python::llun_error;
+use crate::python::response::PyResponse;
+use crate::python::rules::PyRuleSet;
+use crate::rules::RuleSet;
+
+/// the id a mock scanner is registered under
+const MOCK_ID: &str = "mock";
+
+/// runs scans against a provider, or a mock standing in for one
+#[pyclass(name = "Scanner", module = "llun.llun", frozen)]
+pub struct PyScanner {
+    manager: Arc<ScannerManager>,
+    provider: String,
+}
+
+#[pymethods]
+impl PyScanner {
+    /// a scanner for the given provider, configured from the environment like the cli
+    #[new]
+    #[pyo3(signature = (provider = "openai"))]
+    fn new(provider: &str) -> PyResult<Self> {
+        let manager = ScannerManager::new();
+        let provider = ScannerManager::canonical_id(provider).to_string();
+        if !manager.ids().any(|id| id == provider) {
+            return Err(PyValueError::new_err(format!(
+                "Unknown provider '{}', expected one of: {}",
+                provider,
+                manager.ids().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        Ok(Self {
+            manager: Arc::new(manager),
+            provider,
+        })
+    }
+
+    /// a scanner that answers every scan with the given response, without calling any provider
+    #[staticmethod]
+    #[pyo3(signature = (response = PyResponse::default()))]
+    fn mock(response: PyResponse) -> Self {
+        let mut manager = ScannerManager::empty();
+        manager.register_scanner(MOCK_ID, MockScanner::new(response.response));
+
+        Self {
+            manager: Arc::new(manager),
+            provider: MOCK_ID.to_string(),
+        }
+    }
+
+    /// scan the files against the rules, returning an awaitable for the Response
+    /// like any asyncio awaitable, this has to be called with an event loop running
+    #[pyo3(signature = (rules, files, *, model, production_mode = false, context = None))]
+    fn scan<'py>(
+        &self,
+        py: Python<'py>,
+        rules: &PyRuleSet,
+        files: &PyFileSet,
+        model: String,
+        production_mode: bool,
+        context: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let scan = self.prepare_scan(rules, files, model, production_mode, context)?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, scan)
+    }
+
+    /// the same as 'scan', but waits for the Response, letting other python threads run meanwhile
+    #[pyo3(signature = (rules, files, *, model, production_mode = false, context = None))]
+    fn scan_blocking(
+        &self,
+        py: Python<'_>,
+        rules: &PyRuleSet,
+        files: &PyFileSet,
+        model: String,
+        production_mode: bool,
+        context: Option<String>,
+    ) -> PyResult<PyResponse> {
+        let scan = self.prepare_scan(rules, files, model, production_mode, context)?;
+
+        py.allow_threads(|| pyo3_async_runtimes::tokio::get_runtime().block_on(scan))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Scanner({:?})", self.provider)
+    }
+}
+
+impl PyScanner {
+    /// build the prompts while we still hold the python objects, so the scan itself owns everything it needs
+    fn prepare_scan(
+        &self,
+        rules: &PyRuleSet,
+        files: &PyFileSet,
+        model: String,
+        production_mode: bool,
+        context: Option<String>,
+    ) -> PyResult<impl Future<Output = PyResult<PyResponse>> + Send + 'static> {
+        let prompt_manager =
+            PromptManager::new(&rules.rules, &files.files, &context).map_err(llun_error)?;
+
+        Ok(run_scan(
+            Arc::clone(&self.manager),
+            self.provider.clone(),
+            prompt_manager,
+            Arc::clone(&rules.rules),
+            model,
+            production_mode,
+        ))
+    }
+}
+
+async fn run_scan(
+    manager: Arc<ScannerManager>,
+    provider: String,
+    prompt_manager: PromptManager,
+    rules: Arc<RuleSet>,
+    model: String,
+    production_mode: bool,
+) -> PyResult<PyResponse> {
+    let response = manager
+        .run_scan(
+            &prompt_manager.system_prompt_scan,
+            &prompt_manager.user_prompt,
+            &model,
+            &prompt_manager.system_prompt_consistency,
+            &provider,
+            production_mode,
+        )
+        .await
+        .map_err(llun_error)?;
+
+    Ok(PyResponse {
+        response: rules.apply_severities(response),
+    })
+}
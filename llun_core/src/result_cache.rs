@@ -0,0 +1,220 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+use crate::api_client::DetectedIssue;
+use crate::content_hash::content_hash;
+use crate::files::File;
+use crate::rules::RuleSet;
+
+/// where results are cached, under the project root (like llun.toml). never checked itself
+pub const CACHE_DIR: &str = ".llun_cache";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResultCacheError {
+    #[error("Failed to write to the cache at {0}")]
+    WriteError(String, #[source] io::Error),
+    #[error("Failed to clear the cache at {0}")]
+    ClearError(String, #[source] io::Error),
+    #[error("Failed to serialise the cached issues")]
+    SerialiseError(#[from] serde_json::Error),
+}
+
+/// everything other than the file itself that decides what the model says about it
+/// change any of it and every file is sent again
+pub struct CacheScope<'a> {
+    pub rules: &'a RuleSet,
+    pub model: &'a str,
+    pub provider: Option<&'a str>,
+    pub prompt_version: &'a str,
+    pub context: Option<&'a str>,
+    pub production_mode: bool,
+}
+
+impl CacheScope<'_> {
+    fn hash(&self) -> String {
+        let mut rule_hashes: Vec<String> = self.rules.iter().map(|rule| rule.content_hash()).collect();
+        rule_hashes.sort();
+
+        content_hash(format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            rule_hashes.join(","),
+            self.model,
+            self.provider.unwrap_or_default(),
+            self.prompt_version,
+            self.context.unwrap_or_default(),
+            self.production_mode
+        ))
+    }
+}
+
+/// the issues found in each file on earlier runs, so unchanged files dont have to be paid for twice
+/// one json file per entry, named by the hash of the file and the scope it was scanned in
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+    scope: String,
+}
+
+impl ResultCache {
+    pub fn new(dir: impl Into<PathBuf>, scope: &CacheScope) -> Self {
+        Self {
+            dir: dir.into(),
+            scope: scope.hash(),
+        }
+    }
+
+    /// the issues found last time this exact file was scanned in this scope, if it has been
+    /// an unreadable entry is treated as missing, it'll be overwritten once the file is scanned again
+    pub fn get(&self, file: &File) -> Option<Vec<DetectedIssue>> {
+        let path = self.entry_path(file);
+        let content = fs::read_to_string(&path).ok()?;
+
+        match serde_json::from_str(&content) {
+            Ok(issues) => Some(issues),
+            Err(e) => {
+                debug!("Ignoring unreadable cache entry {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// remember the issues found in a file, an empty list is worth remembering too
+    pub fn put(&self, file: &File, issues: &[DetectedIssue]) -> Result<(), ResultCacheError> {
+        if !self.dir.exists() {
+            fs::create_dir_all(&self.dir)
+                .map_err(|e| ResultCacheError::WriteError(self.dir.display().to_string(), e))?;
+            // keep it out of version control without the user having to
+            fs::write(self.dir.join(".gitignore"), "*\n")
+                .map_err(|e| ResultCacheError::WriteError(self.dir.display().to_string(), e))?;
+        }
+
+        let path = self.entry_path(file);
+        fs::write(&path, serde_json::to_string(issues)?)
+            .map_err(|e| ResultCacheError::WriteError(path.display().to_string(), e))
+    }
+
+    /// delete the cache and everything in it, returning whether there was one to delete
+    pub fn clear(dir: &Path) -> Result<bool, ResultCacheError> {
+        if !dir.exists() {
+            return Ok(false);
+        }
+
+        fs::remove_dir_all(dir)
+            .map_err(|e| ResultCacheError::ClearError(dir.display().to_string(), e))?;
+        Ok(true)
+    }
+
+    /// issues carry the files name, so the same content under another name is another entry
+    fn entry_path(&self, file: &File) -> PathBuf {
+        let key = content_hash(format!("{}\n{}\n{}", self.scope, file.name, file.content_hash()));
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::result_large_err)] // figment::Jail closures have to return a bare figment::Error
+mod tests {
+    use super::*;
+    use crate::rules::RuleManager;
+    use figment::Jail;
+
+    fn rule_set(rule_codes: &[&str]) -> RuleSet {
+        let rule_codes = rule_codes.iter().map(|code| code.to_string()).collect();
+        RuleManager::new()
+            .and_then(|manager| manager.load_from_cli(rule_codes, Vec::new(), Vec::new()))
+            .unwrap()
+    }
+
+    fn scope<'a>(rules: &'a RuleSet, model: &'a str) -> CacheScope<'a> {
+        CacheScope {
+            rules,
+            model,
+            provider: Some("openai"),
+            prompt_version: "1",
+            context: None,
+            production_mode: false,
+        }
+    }
+
+    fn file(content: &str) -> File {
+        File {
+            name: "a.py".to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn issue() -> DetectedIssue {
+        DetectedIssue {
+            rule_code: "SOLID01".to_string(),
+            name: "Single Responsibility".to_string(),
+            file_path: "a.py".to_string(),
+            brief_description: "does too much".to_string(),
+            explanation: "".to_string(),
+            suggested_alternative: "".to_string(),
+            code_snippet: "".to_string(),
+            severity: Default::default(),
+        }
+    }
+
+    #[test]
+    fn unchanged_file_is_a_hit() {
+        Jail::expect_with(|_jail| {
+            let rules = rule_set(&["SOLID01"]);
+            let cache = ResultCache::new(CACHE_DIR, &scope(&rules, "gpt-4o"));
+
+            cache.put(&file("x = 1"), &[issue()]).unwrap();
+            let issues = cache.get(&file("x = 1")).unwrap();
+
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].rule_code, "SOLID01");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn changed_or_unseen_file_is_a_miss() {
+        Jail::expect_with(|_jail| {
+            let rules = rule_set(&["SOLID01"]);
+            let cache = ResultCache::new(CACHE_DIR, &scope(&rules, "gpt-4o"));
+
+            assert!(cache.get(&file("x = 1")).is_none());
+            cache.put(&file("x = 1"), &[]).unwrap();
+            assert!(cache.get(&file("x = 2")).is_none());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn changed_scope_invalidates_entries() {
+        Jail::expect_with(|_jail| {
+            let rules = rule_set(&["SOLID01"]);
+            ResultCache::new(CACHE_DIR, &scope(&rules, "gpt-4o"))
+                .put(&file("x = 1"), &[issue()])
+                .unwrap();
+
+            let other_model = ResultCache::new(CACHE_DIR, &scope(&rules, "gpt-4o-mini"));
+            assert!(other_model.get(&file("x = 1")).is_none());
+
+            let more_rules = rule_set(&["SOLID01", "SOLID02"]);
+            let other_rules = ResultCache::new(CACHE_DIR, &scope(&more_rules, "gpt-4o"));
+            assert!(other_rules.get(&file("x = 1")).is_none());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn clear_removes_the_cache() {
+        Jail::expect_with(|_jail| {
+            let rules = rule_set(&["SOLID01"]);
+            let cache = ResultCache::new(CACHE_DIR, &scope(&rules, "gpt-4o"));
+            cache.put(&file("x = 1"), &[]).unwrap();
+
+            assert!(ResultCache::clear(Path::new(CACHE_DIR)).unwrap());
+            assert!(cache.get(&file("x = 1")).is_none());
+            assert!(!ResultCache::clear(Path::new(CACHE_DIR)).unwrap());
+            Ok(())
+        });
+    }
+}
@@ -1,7 +1,9 @@
 pub mod rule;
 pub mod rule_manager;
 pub mod rule_set;
+pub mod severity;
 
-pub use rule::{Rule, RuleError};
-pub use rule_manager::{RuleManager, RuleManagerError};
+pub use rule::{Rule, RuleError, RuleExample};
+pub use rule_manager::{RuleManager, RuleManagerError, RuleSource};
 pub use rule_set::RuleSet;
+pub use severity::Severity;
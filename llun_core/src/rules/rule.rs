@@ -1,18 +1,22 @@
 use std::fmt;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::content_hash::content_hash;
+use crate::rules::Severity;
+
 #[derive(Debug, thiserror::Error)]
 pub enum RuleError {
     #[error("Requested rule doesn't exist")]
     RuleNotFound(),
     #[error("Rule file cant be translated to UTF-8")]
     RuleNotDecodable(),
-    #[error("Rule failed to be read from json {0}")]
+    #[error("Rule failed to be read from json")]
     RuleReadError(#[from] serde_json::Error),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RuleExample {
     pub violation: String,
     pub better: String,
@@ -27,6 +31,8 @@ pub struct Rule {
     pub risk_if_violated: String,
     #[serde(default)]
     pub examples: Vec<RuleExample>,
+    #[serde(default)]
+    pub severity: Severity,
 }
 
 impl Rule {
@@ -37,6 +43,16 @@ impl Rule {
 
         Ok(rule)
     }
+
+    /// the family a rule belongs to, i.e. 'SOLID' for 'SOLID01'
+    pub fn family(&self) -> &str {
+        self.rule_code.trim_end_matches(|c: char| c.is_ascii_digit())
+    }
+
+    /// hash of the rule as the model is shown it, so an edited custom rule hashes differently
+    pub fn content_hash(&self) -> String {
+        content_hash(self.to_string())
+    }
 }
 
 impl fmt::Display for Rule {
@@ -44,6 +60,7 @@ impl fmt::Display for Rule {
         writeln!(f, "## {} - {}", self.rule_code, self.name)?;
         writeln!(f, "*{}*", self.description)?;
         writeln!(f, "**Risk if violated:** {}", self.risk_if_violated)?;
+        writeln!(f, "**Severity:** {}", self.severity)?;
         for example in &self.examples {
             writeln!(
                 f,
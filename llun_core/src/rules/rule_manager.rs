@@ -1,21 +1,50 @@
 use crate::data::RULES_DIR;
-use crate::rules::{Rule, RuleSet};
-use std::collections::HashSet;
+use crate::rules::{Rule, RuleError, RuleSet};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
 // claude suggested these custom errors
 #[derive(Debug, thiserror::Error)]
 pub enum RuleManagerError {
+    /// the unknown rule, and every family that does exist
     #[error("Invalid rule name: {0}")]
-    InvalidRule(String),
+    InvalidRule(String, String),
     #[error("Failed to load default rules: {0}")]
     DefaultRulesError(String),
     #[error("Failed to load ruleset: {0}")]
     RuleSetLoadError(String),
+    #[error("User-defined rule '{0}' conflicts with built-in rule")]
+    ConflictingRule(String),
+    #[error("Rule '{0}' couldnt be loaded")]
+    InvalidRuleFile(String, #[source] RuleError),
     #[error("No rules available in directory")]
     NoRulesAvailable,
 }
 
+impl RuleManagerError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            RuleManagerError::InvalidRule(rule, families) => Some(format!(
+                "pick a rule (i.e. 'SOLID01') or family from {}, or define your own in llun/{}.json",
+                families, rule
+            )),
+            RuleManagerError::ConflictingRule(rule) => Some(format!(
+                "rename llun/{}.json, built-in rule codes cant be redefined",
+                rule
+            )),
+            RuleManagerError::InvalidRuleFile(rule, _) => Some(format!(
+                "check llun/{}.json has a 'name', 'description' and 'risk_if_violated', as in the readme",
+                rule
+            )),
+            _ => None,
+        }
+    }
+}
+
 /// The cli / toml values that a user can use to control rules
 #[derive(Debug, Default, Clone)]
 pub struct RuleSelectionConfig {
@@ -24,20 +53,63 @@ pub struct RuleSelectionConfig {
     pub ignore: Vec<String>,
 }
 
-#[derive(Debug, Default)]
+/// where a rule was defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleSource {
+    /// shipped with llun
+    BuiltIn,
+    /// a json file in the projects 'llun' folder
+    UserDefined,
+}
+
+impl fmt::Display for RuleSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RuleSource::BuiltIn => "built-in",
+            RuleSource::UserDefined => "user-defined",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// the folder user-defined rules live in, relative to the project root
+pub const USER_RULES_DIR: &str = "llun";
+
+#[derive(Debug)]
 pub struct RuleManager {
     valid_rules: HashSet<String>,
+    user_rules_dir: PathBuf,
 }
 
 impl RuleManager {
+    /// built-in rules, plus any user-defined ones under the working directory
     pub fn new() -> Result<Self, RuleManagerError> {
-        let valid_rules = Self::get_valid_rules()?;
+        Self::in_dir(Path::new("."))
+    }
+
+    /// built-in rules, plus any user-defined ones under the given project root
+    pub fn in_dir(root: &Path) -> Result<Self, RuleManagerError> {
+        let user_rules_dir = root.join(USER_RULES_DIR);
+        let valid_rules = Self::valid_rules_in(&user_rules_dir)?;
 
-        Ok(Self { valid_rules })
+        Ok(Self {
+            valid_rules,
+            user_rules_dir,
+        })
+    }
+
+    /// every rule code that can be selected, built-in or user-defined
+    pub fn valid_rules(&self) -> &HashSet<String> {
+        &self.valid_rules
     }
 
     /// get list of rules files from the rules folder
     pub fn get_valid_rules() -> Result<HashSet<String>, RuleManagerError> {
+        Self::valid_rules_in(Path::new(USER_RULES_DIR))
+    }
+
+    fn valid_rules_in(user_rules_dir: &Path) -> Result<HashSet<String>, RuleManagerError> {
         let mut valid_rules: HashSet<String> = RULES_DIR
             .files()
             .filter(|file| file.path().extension().and_then(|s| s.to_str()) == Some("json"))
@@ -49,7 +121,7 @@ impl RuleManager {
             })
             .collect();
 
-        Self::add_user_defined_rules(&mut valid_rules)?;
+        Self::add_user_defined_rules(&mut valid_rules, user_rules_dir)?;
 
         if valid_rules.is_empty() {
             return Err(RuleManagerError::RuleSetLoadError(
@@ -66,18 +138,16 @@ impl RuleManager {
     /// thats not *for* you
     pub fn add_user_defined_rules(
         valid_rules: &mut HashSet<String>,
+        user_rules_dir: &Path,
     ) -> Result<(), RuleManagerError> {
-        if let Ok(entries) = std::fs::read_dir("llun") {
+        if let Ok(entries) = std::fs::read_dir(user_rules_dir) {
             for entry in entries.flatten() {
                 if let Some(name) = entry.path().file_stem().and_then(|s| s.to_str())
                     && entry.path().extension().and_then(|s| s.to_str()) == Some("json")
                 {
                     let rule_name = name.to_string();
                     if valid_rules.contains(&rule_name) {
-                        return Err(RuleManagerError::RuleSetLoadError(format!(
-                            "User-defined rule '{}' conflicts with built-in rule",
-                            rule_name
-                        )));
+                        return Err(RuleManagerError::ConflictingRule(rule_name));
                     }
                     valid_rules.insert(rule_name);
                 }
@@ -86,6 +156,23 @@ impl RuleManager {
         Ok(())
     }
 
+    /// user-defined rules cant share a code with a built-in one, so the code is enough to tell
+    pub fn rule_source(rule_code: &str) -> RuleSource {
+        if RULES_DIR.get_file(format!("{}.json", rule_code)).is_some() {
+            RuleSource::BuiltIn
+        } else {
+            RuleSource::UserDefined
+        }
+    }
+
+    /// every available rule, built-in and user-defined, ordered by code
+    pub fn load_all(&self) -> Result<RuleSet, RuleManagerError> {
+        let mut rule_codes: Vec<String> = self.valid_rules.iter().cloned().collect();
+        rule_codes.sort();
+
+        self.load_from_cli(rule_codes, Vec::new(), Vec::new())
+    }
+
     /// get the final list of selected rules based on the inputs in the config
     /// combines selected rules and extended selection rules
     /// expands any selected rule 'families' out into their underlying rules
@@ -102,7 +189,7 @@ impl RuleManager {
         for rule in &selected_rules {
             if rule.len() >= 2 && rule.chars().rev().take(2).all(|c| c.is_ascii_digit()) {
                 if !self.valid_rules.contains(rule) {
-                    return Err(RuleManagerError::InvalidRule(rule.clone()));
+                    return Err(self.invalid_rule(rule));
                 }
                 expanded_rules.push(rule.clone());
             } else { // if youve picked a rule family rather than a rule
@@ -113,7 +200,7 @@ impl RuleManager {
                     .collect();
                 
                 if matching_rules.is_empty() {
-                    return Err(RuleManagerError::InvalidRule(rule.clone()));
+                    return Err(self.invalid_rule(rule));
                 }
                 
                 expanded_rules.extend(matching_rules);
@@ -136,7 +223,7 @@ impl RuleManager {
             let filename = format!("{}.json", rule_code);
 
             let contents =
-                if let Ok(local_contents) = std::fs::read_to_string(format!("llun/{}", filename)) {
+                if let Ok(local_contents) = std::fs::read_to_string(self.user_rules_dir.join(&filename)) {
                     local_contents
                 } else if let Some(file) = RULES_DIR.get_file(&filename) {
                     file.contents_utf8()
@@ -154,15 +241,29 @@ impl RuleManager {
                     )));
                 };
 
-            match Rule::from_json_str(rule_code, &contents) {
+            match Rule::from_json_str(rule_code.clone(), &contents) {
                 Ok(rule) => collection.add_rule(rule),
-                Err(e) => return Err(RuleManagerError::RuleSetLoadError(e.to_string())),
+                Err(e) => return Err(RuleManagerError::InvalidRuleFile(rule_code, e)),
             }
         }
         debug!("Loaded rules: {0}", &collection);
         Ok(collection)
     }
 
+    /// an unknown rule, along with the families that are available instead
+    fn invalid_rule(&self, rule: &str) -> RuleManagerError {
+        let families: BTreeSet<&str> = self
+            .valid_rules
+            .iter()
+            .map(|rule| rule.trim_end_matches(|c: char| c.is_ascii_digit()))
+            .collect();
+
+        RuleManagerError::InvalidRule(
+            rule.to_string(),
+            families.into_iter().collect::<Vec<_>>().join(", "),
+        )
+    }
+
     /// load the ruleset object from cli commands
     pub fn load_from_cli(
         &self,
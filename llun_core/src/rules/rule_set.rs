@@ -1,4 +1,6 @@
 use super::rule::Rule;
+use super::severity::Severity;
+use crate::api_client::Response;
 use std::fmt;
 
 #[derive(Debug, Default)]
@@ -15,6 +17,43 @@ impl RuleSet {
     pub fn add_rule(&mut self, rule: Rule) {
         self.rules.push(rule);
     }
+
+    /// borrowed iteration over the rules
+    pub fn iter(&self) -> std::slice::Iter<'_, Rule> {
+        self.rules.iter()
+    }
+
+    /// keep only the rules matching the predicate
+    pub fn retain<F: FnMut(&Rule) -> bool>(&mut self, f: F) {
+        self.rules.retain(f);
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// severity of a rule in the set, falling back to the default for anything the model made up
+    pub fn severity_of(&self, rule_code: &str) -> Severity {
+        self.rules
+            .iter()
+            .find(|rule| rule.rule_code == rule_code)
+            .map(|rule| rule.severity)
+            .unwrap_or_default()
+    }
+
+    /// stamp each detected issue with its rules severity
+    /// the model never decides severity, the rule files do
+    pub fn apply_severities(&self, mut response: Response) -> Response {
+        for issue in &mut response.detected_issues {
+            issue.severity = self.severity_of(&issue.rule_code);
+        }
+
+        response
+    }
 }
 
 /// we will (hopefully) use display to insert into a markdown message?
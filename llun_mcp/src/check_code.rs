@@ -0,0 +1,224 @@
+use rmcp::model::{ErrorCode, ErrorData as McpError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info};
+
+use llun_core::api_client::{Response, ScanProgress, ScannerManager};
+use llun_core::files::{File, FileManager};
+use llun_core::{CheckConfig, Llun, LlunCoreError, Report};
+
+use crate::config::{describe, internal_error, load_config, load_config_with_nested, nested_config_chain};
+use crate::progress::{CheckProgress, Progress};
+use crate::workspace::Workspace;
+
+/// rmcp answers every request, even one the client cancelled, so it gets the json-rpc code for
+/// a cancelled request (borrowed from lsp, mcp doesnt define one) rather than looking like our fault
+pub const REQUEST_CANCELLED: ErrorCode = ErrorCode(-32800);
+
+/// what the agent wants checked, either files on disk, inline code or both
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CheckCodeArgs {
+    /// files or directories to check, relative to the workspace root
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+
+    /// code to check directly, i.e. a file that hasnt been saved yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// name to report the inline code under, also used to match per-file ignores i.e. 'src/main.py'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+
+    /// what the code is for, to help llun understand the point of it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+/// the same pipeline as 'llun check', minus the output formatting
+/// files are grouped by the nested llun.tomls above them, and each group checked against its own config
+/// cancelling drops the scan, along with any requests still waiting on the provider
+pub async fn check_code(
+    args: CheckCodeArgs,
+    workspace: &Workspace,
+    progress: Progress,
+    ct: CancellationToken,
+) -> Result<Response, McpError> {
+    if args.paths.is_empty() && args.code.is_none() {
+        return Err(McpError::invalid_params(
+            "Provide 'paths' to check, or 'code' with a 'filename'",
+            None,
+        ));
+    }
+    if args.code.is_some() && args.filename.is_none() {
+        return Err(McpError::invalid_params(
+            "Inline 'code' needs a 'filename' to report issues against",
+            None,
+        ));
+    }
+
+    let groups = group_by_config(&args, workspace)?;
+    let inline = args.code.zip(args.filename);
+
+    let mut checks = Vec::new();
+    for (config, files) in groups {
+        let mut builder = Llun::builder().root(workspace.root()).config(CheckConfig {
+            path: files.clone(),
+            ..config
+        });
+        if let Some((code, filename)) = &inline
+            && files.contains(&PathBuf::from(filename))
+        {
+            builder = builder.file(File {
+                name: filename.clone(),
+                content: code.clone(),
+            });
+        }
+        checks.push(builder.build().map_err(check_error)?);
+    }
+
+    let requests = checks
+        .iter()
+        .map(|llun| ScannerManager::request_count(llun.config().production_mode))
+        .max()
+        .unwrap_or(1);
+    let progress = CheckProgress::new(progress, requests, checks.len());
+    let on_progress = |event| progress.scan(event);
+    let report = tokio::select! {
+        result = check_all(&checks, &on_progress) => result.map_err(check_error)?,
+        _ = ct.cancelled() => {
+            info!("Check cancelled by the client");
+            return Err(McpError::new(REQUEST_CANCELLED, "Check was cancelled by the client", None));
+        }
+    };
+    progress.finish().await;
+
+    Ok(report.response)
+}
+
+/// the files the agent asked for, split up by the config that applies to them the same way 'llun check' does
+/// directories are walked up front so every file lands in its own packages group
+fn group_by_config(
+    args: &CheckCodeArgs,
+    workspace: &Workspace,
+) -> Result<Vec<(CheckConfig, Vec<PathBuf>)>, McpError> {
+    let paths = args
+        .paths
+        .iter()
+        .map(|path| workspace.resolve(path))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| McpError::invalid_params(describe(&e), None))?;
+    let inline_name = args
+        .filename
+        .as_deref()
+        .filter(|_| args.code.is_some())
+        .map(|filename| workspace.resolve_name(filename))
+        .transpose()
+        .map_err(|e| McpError::invalid_params(describe(&e), None))?;
+
+    // only 'context' overlaps with the config, the rest is ignored when extracting
+    let root_config: CheckConfig = load_config(workspace, args)?;
+    let root = workspace.root();
+    let files = FileManager::collect_from_cli(
+        paths.iter().map(|path| root.join(path)).collect(),
+        root_config.exclude.iter().map(|path| root.join(path)).collect(),
+        root_config.no_respect_gitignore,
+    )
+    .map_err(|e| McpError::invalid_params(format!("Failed to find files: {}", describe(&e)), None))?;
+
+    let mut files_by_chain: BTreeMap<Vec<PathBuf>, Vec<PathBuf>> = BTreeMap::new();
+    for file in files {
+        let file = file.strip_prefix(&root).unwrap_or(&file).to_path_buf();
+        files_by_chain
+            .entry(nested_config_chain(workspace, &file))
+            .or_default()
+            .push(file);
+    }
+    // inline code is named as the agent gave it, it replaces that file if its on disk too
+    if let (Some(inline_name), Some(filename)) = (inline_name, &args.filename) {
+        for files in files_by_chain.values_mut() {
+            files.retain(|file| *file != inline_name);
+        }
+        files_by_chain.retain(|_, files| !files.is_empty());
+        files_by_chain
+            .entry(nested_config_chain(workspace, &inline_name))
+            .or_default()
+            .push(PathBuf::from(filename));
+    }
+
+    files_by_chain
+        .into_iter()
+        .map(|(chain, files)| {
+            debug!("Config chain {:?} applies to {} files", chain, files.len());
+            Ok((load_config_with_nested(workspace, args, &chain)?, files))
+        })
+        .collect()
+}
+
+/// check each group in turn, and combine the results
+async fn check_all(
+    checks: &[Llun],
+    on_progress: &(dyn Fn(ScanProgress) + Send + Sync),
+) -> Result<Report, LlunCoreError> {
+    let mut combined_report: Option<Report> = None;
+
+    for llun in checks {
+        let report = llun.check_with_progress(on_progress).await?;
+        match &mut combined_report {
+            Some(combined_report) => combined_report.merge(report),
+            None => combined_report = Some(report),
+        }
+    }
+
+    Ok(combined_report.unwrap_or_default())
+}
+
+/// blame the agent for anything it could fix by calling again differently, and llun for the rest
+fn check_error(e: LlunCoreError) -> McpError {
+    match e {
+        LlunCoreError::CheckError(_) | LlunCoreError::RuleManagerError(_) => {
+            McpError::invalid_params(describe(&e), None)
+        }
+        LlunCoreError::FileManagerError(_) => {
+            McpError::invalid_params(format!("Failed to find files: {}", describe(&e)), None)
+        }
+        LlunCoreError::PerFileIgnorerError(_) => {
+            McpError::invalid_params(format!("Invalid per-file-ignores: {}", describe(&e)), None)
+        }
+        e => internal_error("Check failed")(e),
+    }
+}
+
+/// a plain text overview for clients that dont read structured content
+pub fn summarise(response: &Response) -> String {
+    if response.detected_issues.is_empty() {
+        return "No issues detected.".to_string();
+    }
+
+    let files: BTreeSet<&str> = response
+        .detected_issues
+        .iter()
+        .map(|issue| issue.file_path.as_str())
+        .collect();
+    let mut summary = format!(
+        "Found {} issue(s) across {} file(s):\n",
+        response.detected_issues.len(),
+        files.len()
+    );
+
+    for issue in &response.detected_issues {
+        summary.push_str(&format!(
+            "\n- [{}] {} ({}) in {}: {}\n  Suggestion: {}\n",
+            issue.severity,
+            issue.rule_code,
+            issue.name,
+            issue.file_path,
+            issue.brief_description,
+            issue.suggested_alternative
+        ));
+    }
+
+    summary
+}
@@ -0,0 +1,121 @@
+use figment::{
+    Figment,
+    providers::{Format, Serialized, Toml},
+};
+use rmcp::model::ErrorData as McpError;
+use serde::{Serialize, de::DeserializeOwned};
+use std::path::{Path, PathBuf};
+use tracing::{debug, error, warn};
+
+use llun_core::ErrorReport;
+use llun_core::data::DEFAULT_CONFIG;
+use llun_core::env_provider::LlunEnv;
+
+use crate::workspace::Workspace;
+
+const CONFIG_FILENAME: &str = "llun.toml";
+const PYPROJECT_FILENAME: &str = "pyproject.toml";
+
+/// read the users config the same way the cli does, with any tool arguments layered on top
+/// config files are found from the workspace root, and cached until they change
+pub fn load_config<T, O>(workspace: &Workspace, overrides: &O) -> Result<T, McpError>
+where
+    T: DeserializeOwned,
+    O: Serialize,
+{
+    load_config_with_nested(workspace, overrides, &[])
+}
+
+/// the config that applies to one file, including any nested llun.tomls above it
+/// the path should already be relative to the root, i.e. from 'Workspace::resolve'
+pub fn load_config_for_file<T, O>(
+    workspace: &Workspace,
+    overrides: &O,
+    file_path: &Path,
+) -> Result<T, McpError>
+where
+    T: DeserializeOwned,
+    O: Serialize,
+{
+    let nested_configs = nested_config_chain(workspace, file_path);
+    load_config_with_nested(workspace, overrides, &nested_configs)
+}
+
+/// defaults < pyproject.toml < llun.toml < nested llun.tomls < LLUN_* env vars < tool arguments
+pub fn load_config_with_nested<T, O>(
+    workspace: &Workspace,
+    overrides: &O,
+    nested_configs: &[PathBuf],
+) -> Result<T, McpError>
+where
+    T: DeserializeOwned,
+    O: Serialize,
+{
+    // default values are set in the data file in the library
+    let mut figment = Figment::new().merge(Toml::string(DEFAULT_CONFIG));
+    if let Some(pyproject) = workspace.read_config(Path::new(PYPROJECT_FILENAME)) {
+        figment = figment.merge(Figment::from(Toml::string(&pyproject)).focus("tool.llun"));
+    }
+    if let Some(llun_toml) = workspace.read_config(Path::new(CONFIG_FILENAME)) {
+        figment = figment.merge(Toml::string(&llun_toml));
+    }
+    for nested_config in nested_configs {
+        if let Some(nested_config) = workspace.read_config(nested_config) {
+            figment = figment.merge(Toml::string(&nested_config));
+        }
+    }
+
+    let config = figment
+        .merge(LlunEnv::new())
+        .merge(Serialized::defaults(overrides))
+        .extract()
+        .map_err(|e| {
+            error!("Failed to load config: {}", e);
+            McpError::internal_error(format!("Failed to load configuration: {}", e), None)
+        })?;
+    debug!("Read user arguments from tomls...");
+
+    Ok(config)
+}
+
+/// every nested llun.toml between the workspace root and the file, outermost first
+/// same walk as the cli, the root llun.toml is always loaded so is left out. the file is
+/// relative to the root, so the walk ends there rather than carrying on up the filesystem
+pub fn nested_config_chain(workspace: &Workspace, file_path: &Path) -> Vec<PathBuf> {
+    if file_path.is_absolute() {
+        warn!("Not looking for nested configs above {}, it isnt relative to the root", file_path.display());
+        return Vec::new();
+    }
+
+    let mut chain: Vec<PathBuf> = file_path
+        .ancestors()
+        .skip(1)
+        .take_while(|directory| !(directory.as_os_str().is_empty() || *directory == Path::new(".")))
+        .map(|directory| directory.join(CONFIG_FILENAME))
+        .filter(|config_path| workspace.read_config(config_path).is_some())
+        .collect();
+
+    chain.reverse();
+    chain
+}
+
+/// the impl requires errors of a certain type, so log and wrap whatever the library gave us
+/// the whole chain goes into the message, along with a hint if llun has one
+pub fn internal_error<E: std::error::Error + 'static>(
+    message: &'static str,
+) -> impl FnOnce(E) -> McpError {
+    move |e| {
+        let detail = describe(&e);
+        error!("{}: {}", message, detail);
+        McpError::internal_error(format!("{}: {}", message, detail), None)
+    }
+}
+
+/// an error and its causes on one line, followed by how to fix it if llun knows
+pub fn describe(error: &(dyn std::error::Error + 'static)) -> String {
+    let report = ErrorReport::new(error);
+    match report.hint() {
+        Some(hint) => format!("{} ({})", report, hint),
+        None => report.to_string(),
+    }
+}
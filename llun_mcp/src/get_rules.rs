@@ -0,0 +1,95 @@
+use rmcp::model::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use llun_core::per_file_ignorer::PerFileIgnorer;
+use llun_core::rules::RuleSet;
+
+use crate::config::{describe, internal_error, load_config, load_config_for_file};
+use crate::resources::rule_json;
+use crate::workspace::Workspace;
+
+/// how the rules should be written back to the agent
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RulesFormat {
+    /// readable markdown, the same as the rules section of a review prompt
+    #[default]
+    Markdown,
+    /// each rule as a json object, with its code, examples, risk and severity
+    Json,
+}
+
+/// which rules the agent wants, all optional so no arguments gets the users configured selection
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetRulesArgs {
+    /// rules or rule families to use instead of the configured selection i.e. ['SOLID', 'DOMAIN01']
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub select: Vec<String>,
+
+    /// rules or rule families to add to the selection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extend_select: Vec<String>,
+
+    /// rules or rule families to leave out of the selection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<String>,
+
+    /// only return the rules that apply to this file, relative to the workspace root i.e. 'src/main.py'.
+    /// takes nested llun.tomls and per-file-ignores into account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+
+    /// 'markdown' (default) or 'json'
+    #[serde(default)]
+    pub format: RulesFormat,
+}
+
+/// the parts of the users config a rule selection needs
+#[derive(Debug, Serialize, Deserialize)]
+struct GetRulesConfig {
+    #[serde(default)]
+    select: Vec<String>,
+    #[serde(default)]
+    extend_select: Vec<String>,
+    #[serde(default)]
+    ignore: Vec<String>,
+    #[serde(default)]
+    per_file_ignores: Vec<String>,
+}
+
+/// the users selection, adjusted by the agents arguments and narrowed to a single file if asked
+pub fn get_rules(args: &GetRulesArgs, workspace: &Workspace) -> Result<RuleSet, McpError> {
+    let file_path = args
+        .file_path
+        .as_deref()
+        .map(|file_path| workspace.resolve_name(file_path))
+        .transpose()
+        .map_err(|e| McpError::invalid_params(describe(&e), None))?;
+
+    // 'file_path' and 'format' dont overlap with the config, so are ignored when extracting
+    let config: GetRulesConfig = match &file_path {
+        Some(file_path) => load_config_for_file(workspace, args, file_path)?,
+        None => load_config(workspace, args)?,
+    };
+
+    let rule_manager = workspace.rule_manager().map_err(internal_error("Failed to initialize rules"))?;
+    let mut rules = rule_manager
+        .load_from_cli(config.select, config.extend_select, config.ignore)
+        .map_err(|e| McpError::invalid_params(format!("Invalid rule selection: {}", e), None))?;
+
+    if let Some(file_path) = &file_path {
+        let per_file_ignorer = PerFileIgnorer::new(config.per_file_ignores).map_err(|e| {
+            McpError::invalid_params(format!("Invalid per-file-ignores: {}", e), None)
+        })?;
+        let file_path = file_path.to_string_lossy();
+        rules.retain(|rule| !per_file_ignorer.should_ignore(&file_path, &rule.rule_code));
+    }
+
+    Ok(rules)
+}
+
+/// every rule in full, for agents that asked for json
+pub fn rules_json(rules: &RuleSet) -> Value {
+    json!({ "rules": rules.iter().map(rule_json).collect::<Vec<_>>() })
+}
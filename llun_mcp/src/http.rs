@@ -0,0 +1,143 @@
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use rmcp::transport::{
+    sse_server::{SseServer, SseServerConfig},
+    streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+    },
+};
+use std::{net::SocketAddr, sync::Arc};
+use subtle::ConstantTimeEq;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::LlunServer;
+use crate::workspace::Workspace;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpError {
+    #[error("Refusing to serve on {0} without an auth token, anyone who can reach it could run scans")]
+    Unauthenticated(SocketAddr),
+    #[error("Failed to serve on {0}")]
+    ServeError(SocketAddr, #[source] std::io::Error),
+}
+
+impl HttpError {
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            HttpError::Unauthenticated(_) => Some(
+                "set LLUN_MCP_AUTH_TOKEN (or --auth-token), bind to 127.0.0.1, or pass --allow-unauthenticated if the network is trusted".to_string(),
+            ),
+            HttpError::ServeError(..) => None,
+        }
+    }
+}
+
+/// where the streamable http transport is served
+pub const MCP_PATH: &str = "/mcp";
+/// where legacy sse clients open their event stream
+pub const SSE_PATH: &str = "/sse";
+/// where legacy sse clients post their messages
+pub const MESSAGE_PATH: &str = "/message";
+
+/// routes for both the streamable http and legacy sse transports, every session gets its own server
+/// if a token is given, every request must carry it as 'Authorization: Bearer <token>'
+/// cancelling the returned token closes any open sse sessions
+pub fn router(
+    bind: SocketAddr,
+    auth_token: Option<String>,
+    workspace: Arc<Workspace>,
+) -> (Router, CancellationToken) {
+    let streamable_workspace = Arc::clone(&workspace);
+    let streamable = StreamableHttpService::new(
+        move || Ok(LlunServer::new(Arc::clone(&streamable_workspace))),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    let (sse_server, sse_router) = SseServer::new(SseServerConfig {
+        bind,
+        sse_path: SSE_PATH.to_string(),
+        post_path: MESSAGE_PATH.to_string(),
+        ct: CancellationToken::new(),
+        sse_keep_alive: None,
+    });
+    let ct = sse_server.with_service(move || LlunServer::new(Arc::clone(&workspace)));
+
+    let router = Router::new()
+        .nest_service(MCP_PATH, streamable)
+        .merge(sse_router);
+
+    let router = match auth_token {
+        Some(token) => router.layer(middleware::from_fn_with_state(
+            Arc::<str>::from(format!("Bearer {}", token)),
+            require_bearer,
+        )),
+        None => router,
+    };
+
+    (router, ct)
+}
+
+/// serve both transports on the given address until ctrl-c
+/// anywhere other than loopback needs a token, unless the caller has explicitly allowed going without
+pub async fn serve(
+    bind: SocketAddr,
+    auth_token: Option<String>,
+    allow_unauthenticated: bool,
+    workspace: Arc<Workspace>,
+) -> Result<(), HttpError> {
+    if auth_token.is_none() && !bind.ip().is_loopback() {
+        if !allow_unauthenticated {
+            return Err(HttpError::Unauthenticated(bind));
+        }
+        warn!("Serving on {} without an auth token, anyone who can reach it can run scans", bind);
+    }
+
+    let (router, ct) = router(bind, auth_token, workspace);
+    let listener = tokio::net::TcpListener::bind(bind)
+        .await
+        .map_err(|e| HttpError::ServeError(bind, e))?;
+    info!(
+        "Serving streamable http on http://{}{} and sse on http://{}{}",
+        bind, MCP_PATH, bind, SSE_PATH
+    );
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            let _ = tokio::signal::ctrl_c().await;
+            info!("Shutting down");
+            ct.cancel();
+        })
+        .await
+        .map_err(|e| HttpError::ServeError(bind, e))
+}
+
+/// reject anything without the expected 'Authorization' header
+/// compared in constant time, so the token cant be guessed a byte at a time from response timings
+async fn require_bearer(
+    State(expected): State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorised = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .is_some_and(|value| value.as_bytes().ct_eq(expected.as_bytes()).into());
+
+    if !authorised {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, "Bearer")],
+            "Missing or invalid bearer token",
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
@@ -1,23 +1,56 @@
-use figment::{
-    Figment,
-    providers::{Format, Toml},
-};
 use serde::{Deserialize, Serialize};
 use rmcp::{
+    handler::server::{
+        router::{prompt::PromptRouter, tool::ToolRouter},
+        tool::cached_schema_for_type,
+        wrapper::Parameters,
+    },
     model::*,
-    service::RequestContext,
-    tool, tool_handler, tool_router,
+    service::{NotificationContext, Peer, RequestContext},
+    prompt_handler, tool, tool_handler, tool_router,
     RoleServer, ServerHandler,
 };
 use rmcp::model::ErrorData as McpError;
-use tracing::{info, debug, error};
+use std::sync::Arc;
+use tracing::{Instrument, info, instrument, warn};
+
+use llun_core::api_client::Response;
+use llun_core::rules::RuleSet;
+
+pub mod config;
+use config::{internal_error, load_config};
+
+pub mod check_code;
+use check_code::{CheckCodeArgs, check_code, summarise};
+
+pub mod get_rules;
+use get_rules::{GetRulesArgs, RulesFormat, get_rules, rules_json};
+
+pub mod rule_info;
+use rule_info::{
+    ExplainRuleArgs, ListRulesArgs, RuleDetails, RuleList, explain_rule, list_rules,
+    summarise_details, summarise_list,
+};
+
+pub mod resources;
+use resources::{list_rule_resources, read_rule_resource, rule_templates};
 
-use llun_core::data::DEFAULT_CONFIG;
-use llun_core::rules::RuleManager;
+pub mod prompts;
+
+pub mod logging;
+use logging::Session;
+
+pub mod progress;
+use progress::Progress;
+
+pub mod workspace;
+use workspace::Workspace;
+
+pub mod http;
 
 
 /// Args we want to pull from the users (or our default) toml file.
-#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct RulesArgs {
     /// rules to utilise (overrides default values)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -33,71 +66,217 @@ pub struct RulesArgs {
 }
 
 /// MCP Server
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct LlunServer {
-    tool_router: rmcp::handler::server::router::tool::ToolRouter<LlunServer>
+    tool_router: ToolRouter<LlunServer>,
+    prompt_router: PromptRouter<LlunServer>,
+    workspace: Arc<Workspace>,
+    /// each client gets its own server, this tells them apart for logging
+    session: Arc<Session>,
 }
 
 /// dispatch all 'tool's to our mcp server
 #[tool_router]
 impl LlunServer {
-    pub fn new() -> Self {
+    /// every session on a server shares the same workspace
+    pub fn new(workspace: Arc<Workspace>) -> Self {
         info!("setting up tools");
-        Self { tool_router: Self::tool_router() } // tool_router comes from #[tool_router]
+        // tool_router comes from #[tool_router], prompt_router from #[prompt_router]
+        Self {
+            tool_router: Self::tool_router(),
+            prompt_router: Self::prompt_router(),
+            workspace,
+            session: Arc::new(Session::new()),
+        }
     }
 
-    /// MCP 'tool' for accessing the users selected rules and returning them to the agent
-    #[tool(description = "Get a user defined selection of architectural rules, patterns and principles that should be followed when building new solutions. Call this tool prior to beginning coding or design tasks in order to fully understand the required context for the users specification.")]
-    async fn get_rules(&self) -> Result<CallToolResult, McpError> {
-        // assume user runs this from root... im not sure how else to do it really?
-        let config: RulesArgs = Figment::new()
-            .merge(Toml::string(DEFAULT_CONFIG)) // default values are set in the data file in the library
-            .merge(Toml::file("pyproject.toml").nested())
-            .merge(Toml::file("llun.toml"))
-            .select("tool.llun")
-            .extract()
-            .map_err(|e| {
-                error!("Failed to load config: {}", e);
-                McpError::internal_error(
-                    format!("Failed to load configuration: {}", e),
-                    None,
-                )
-            })?;
-        debug!("Read user arguments from tomls...");
+    /// the rules selected by the users config
+    fn selected_rules(&self) -> Result<RuleSet, McpError> {
+        let config: RulesArgs = load_config(&self.workspace, &RulesArgs::default())?;
 
         // i have to map loads of errors here as the impl requires errors of a certain type.
         // makes it look like way more code that it really is - its just pulling rules from our lib in actuality
-        let rule_manager = RuleManager::new().map_err(|e| {
-            error!("Failed to create RuleManager: {}", e);
-            McpError::internal_error(format!("Failed to initialize rules: {}", e), None)
-        })?;
-        let rules = rule_manager
+        let rule_manager = self.workspace.rule_manager().map_err(internal_error("Failed to initialize rules"))?;
+        rule_manager
             .load_from_cli(config.select, config.extend_select, config.ignore)
-            .map_err(|e| {
-                error!("Failed to load rules: {}", e);
-                McpError::internal_error(format!("Failed to load rules: {}", e), None)
-            })?;
-        let formatted_rules = format!("{}", rules); // should these be formated in other more sexy ways?
-
-        Ok(CallToolResult::success(vec![Content::text(
-            formatted_rules,
-        )]))
+            .map_err(internal_error("Failed to load rules"))
+    }
+
+    /// MCP 'tool' for accessing the users selected rules and returning them to the agent
+    #[tool(description = "Get a user defined selection of architectural rules, patterns and principles that should be followed when building new solutions. Call this tool prior to beginning coding or design tasks in order to fully understand the required context for the users specification. Pass 'file_path' to get only the rules that apply to the file being edited, 'select', 'extend_select' or 'ignore' to adjust the selection, and 'format' of 'json' for structured rules.")]
+    #[instrument(skip_all, fields(session_id = self.session.id()))]
+    async fn get_rules(
+        &self,
+        Parameters(args): Parameters<GetRulesArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let rules = get_rules(&args, &self.workspace)?;
+
+        match args.format {
+            RulesFormat::Markdown => Ok(CallToolResult::success(vec![Content::text(format!(
+                "{}",
+                rules
+            ))])),
+            RulesFormat::Json => {
+                let structured_rules = rules_json(&rules);
+                structured_result(structured_rules.to_string(), &structured_rules)
+            }
+        }
+    }
+
+    /// MCP 'tool' for browsing the available rules without pulling all of them
+    #[tool(
+        description = "List every available architectural rule with its code, name, family, severity, source (built-in or user-defined) and whether the users config selects it. Pass 'family' to list a single family, or 'selected_only' for just the users selection. Use 'explain_rule' to read a rule in full.",
+        output_schema = cached_schema_for_type::<RuleList>()
+    )]
+    #[instrument(skip_all, fields(session_id = self.session.id()))]
+    async fn list_rules(
+        &self,
+        Parameters(args): Parameters<ListRulesArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let list = list_rules(&args, &self.selected_rules()?, &self.workspace)?;
+        structured_result(summarise_list(&list), &list)
+    }
+
+    /// MCP 'tool' for reading a single rule in full
+    #[tool(
+        description = "Explain a single architectural rule by its code i.e. 'DOMAIN02', with its description, the risk if violated, examples of violations and better alternatives, severity and source. Use this to cite the exact rule when making or justifying design decisions.",
+        output_schema = cached_schema_for_type::<RuleDetails>()
+    )]
+    #[instrument(skip_all, fields(session_id = self.session.id()))]
+    async fn explain_rule(
+        &self,
+        Parameters(args): Parameters<ExplainRuleArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        let details = explain_rule(&args, &self.selected_rules()?, &self.workspace)?;
+        structured_result(summarise_details(&details), &details)
+    }
+
+    /// MCP 'tool' for running a full llun check over the agents work
+    #[tool(
+        description = "Review code against the users selected architectural rules, the same way the 'llun check' CLI does. Pass 'paths' to check files on disk, or 'code' with a 'filename' to check code that hasnt been saved. Call this tool after completing coding tasks to verify the work follows the users architecture. Returns the detected issues as structured JSON, with a text summary.",
+        output_schema = cached_schema_for_type::<Response>()
+    )]
+    #[instrument(skip_all, fields(session_id = self.session.id()))]
+    async fn check_code(
+        &self,
+        Parameters(args): Parameters<CheckCodeArgs>,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let progress = Progress::new(context.peer, context.meta.get_progress_token());
+        let response = check_code(args, &self.workspace, progress, context.ct).await?;
+        structured_result(summarise(&response), &response)
+    }
+}
+
+/// a text summary for clients that dont read structured content, plus the structured content itself
+fn structured_result<T: Serialize>(summary: String, value: &T) -> Result<CallToolResult, McpError> {
+    let structured =
+        serde_json::to_value(value).map_err(internal_error("Failed to serialise tool result"))?;
+
+    Ok(CallToolResult {
+        content: vec![Content::text(summary)],
+        structured_content: Some(structured),
+        is_error: Some(false),
+        meta: None,
+    })
+}
+
+impl LlunServer {
+    /// follow the clients workspace, if it told us where that is
+    /// rmcp waits on the initialized notification before it starts reading messages, so asking
+    /// for the roots has to happen in the background or we'd never see the answer
+    fn sync_roots(&self, peer: Peer<RoleServer>) {
+        let supports_roots = peer
+            .peer_info()
+            .is_some_and(|info| info.capabilities.roots.is_some());
+        if self.workspace.is_pinned() || !supports_roots {
+            return;
+        }
+
+        let workspace = Arc::clone(&self.workspace);
+        tokio::spawn(
+            async move {
+                match peer.list_roots().await {
+                    Ok(result) => {
+                        if let Err(e) = workspace.use_client_roots(&result.roots) {
+                            warn!("Ignoring the clients roots: {}", e);
+                        }
+                    }
+                    Err(e) => warn!("Failed to list the clients roots: {}", e),
+                }
+            }
+            .instrument(self.session.span()),
+        );
     }
 }
 
 /// provide the MCP server with the relevant metadata
 #[tool_handler]
+#[prompt_handler]
 impl ServerHandler for LlunServer {
     /// sent to clients when they connect to the llun server
     fn get_info(&self) -> ServerInfo {
         ServerInfo{
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_logging()
+                .enable_prompts()
+                .enable_resources()
+                .enable_tools()
+                .build(),
             server_info: Implementation::from_build_env(),
             instructions: Some("Server which uses the popular 'Llun' tool to provide user defined architectural rules which must be followed when engineering new solutions.".to_string())
         }
     }
 
+    /// the rules index, and a resource for each selected rule
+    #[instrument(skip_all, fields(session_id = self.session.id()))]
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let rules = self.selected_rules()?;
+        Ok(ListResourcesResult::with_all_items(list_rule_resources(&rules)))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(rule_templates()))
+    }
+
+    #[instrument(skip_all, fields(session_id = self.session.id()))]
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let rules = self.selected_rules()?;
+        let rule_manager = self.workspace.rule_manager().map_err(internal_error("Failed to initialize rules"))?;
+        read_rule_resource(&request.uri, &rules, &rule_manager)
+    }
+
+    /// start forwarding llun's logs to the client, at or above the level it asked for
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        logging::listen(self.session.id(), context.peer, request.level);
+        Ok(())
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.sync_roots(context.peer);
+    }
+
+    async fn on_roots_list_changed(&self, context: NotificationContext<RoleServer>) {
+        self.sync_roots(context.peer);
+    }
+
     /// lift and shift from example repo - pretty sure its boilerplate
     async fn initialize(
         &self,
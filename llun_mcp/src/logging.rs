@@ -0,0 +1,218 @@
+use rmcp::{
+    Peer, RoleServer,
+    model::{LoggingLevel, LoggingMessageNotificationParam},
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard, OnceLock};
+use tokio::sync::mpsc;
+use tracing::{
+    Event, Level, Span, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id},
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+/// every session that asked for logs, by session id, with the lowest level it wants
+type Listeners = HashMap<u64, (Peer<RoleServer>, LoggingLevel)>;
+
+/// a log message, and the session it was logged while working for if it was one sessions work
+type QueuedMessage = (Option<u64>, LoggingMessageNotificationParam);
+
+/// tracing is global to the process, so the sessions listening to it have to be too
+static LISTENERS: LazyLock<Mutex<Listeners>> = LazyLock::new(Default::default);
+
+/// log messages waiting to be sent, started the first time a session asks for logs
+static QUEUE: OnceLock<mpsc::UnboundedSender<QueuedMessage>> = OnceLock::new();
+
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// the name of the span field that ties events to a session
+const SESSION_FIELD: &str = "session_id";
+
+/// one clients connection to the server, shared by every clone of its server
+/// its logs stop once the last clone is dropped, i.e. when the client disconnects
+#[derive(Debug)]
+pub struct Session {
+    id: u64,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// run anything done for the session in this, so its logs only go back to that session
+    pub fn span(&self) -> Span {
+        tracing::info_span!("session", session_id = self.id)
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        stop_listening(self.id);
+    }
+}
+
+/// the session a span was opened for, kept in the spans extensions
+struct SessionId(u64);
+
+/// forwards llun's own tracing events to the session they were logged for, if it has set a logging level
+/// events logged outside of any session (i.e. a config file changing) are the servers own, so go to every session
+/// rmcp's events are left out, sending a notification logs and that would loop forever
+pub struct McpLogLayer;
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for McpLogLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = SessionVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(session_id), Some(span)) = (visitor.session_id, ctx.span(id)) {
+            span.extensions_mut().insert(SessionId(session_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let target = event.metadata().target();
+        if !target.starts_with("llun") {
+            return;
+        }
+        let Some(queue) = QUEUE.get() else {
+            return;
+        };
+        let level = logging_level(event.metadata().level());
+        let session_id = ctx.event_scope(event).and_then(|scope| {
+            scope
+                .from_root()
+                .find_map(|span| span.extensions().get::<SessionId>().map(|session| session.0))
+        });
+        let wanted = lock_listeners().iter().any(|(listener_id, (_, wanted_level))| {
+            session_id.is_none_or(|session_id| session_id == *listener_id)
+                && rank(level) >= rank(*wanted_level)
+        });
+        if !wanted {
+            return;
+        }
+
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+
+        // the queue only closes with the runtime, so theres no one left to tell
+        let _ = queue.send((
+            session_id,
+            LoggingMessageNotificationParam {
+                level,
+                logger: Some(target.to_string()),
+                data: Value::Object(visitor.fields),
+            },
+        ));
+    }
+}
+
+/// start sending a session logs at or above the given level, replacing whatever it asked for before
+pub fn listen(session_id: u64, peer: Peer<RoleServer>, level: LoggingLevel) {
+    QUEUE.get_or_init(|| {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(forward(receiver));
+        sender
+    });
+
+    lock_listeners().insert(session_id, (peer, level));
+}
+
+/// stop sending a session logs, i.e. when it disconnects
+pub fn stop_listening(session_id: u64) {
+    lock_listeners().remove(&session_id);
+}
+
+async fn forward(mut receiver: mpsc::UnboundedReceiver<QueuedMessage>) {
+    while let Some((session_id, message)) = receiver.recv().await {
+        let listeners: Vec<(u64, Peer<RoleServer>)> = lock_listeners()
+            .iter()
+            .filter(|(listener_id, (_, level))| {
+                session_id.is_none_or(|session_id| session_id == **listener_id)
+                    && rank(message.level) >= rank(*level)
+            })
+            .map(|(session_id, (peer, _))| (*session_id, peer.clone()))
+            .collect();
+
+        for (session_id, peer) in listeners {
+            if peer.notify_logging_message(message.clone()).await.is_err() {
+                stop_listening(session_id);
+            }
+        }
+    }
+}
+
+fn lock_listeners() -> MutexGuard<'static, Listeners> {
+    LISTENERS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn logging_level(level: &Level) -> LoggingLevel {
+    match *level {
+        Level::ERROR => LoggingLevel::Error,
+        Level::WARN => LoggingLevel::Warning,
+        Level::INFO => LoggingLevel::Info,
+        Level::DEBUG | Level::TRACE => LoggingLevel::Debug,
+    }
+}
+
+/// rmcp doesnt order its levels, these follow the syslog severities the spec uses
+fn rank(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// an events fields as json, with the message under 'message'
+#[derive(Default)]
+struct JsonVisitor {
+    fields: Map<String, Value>,
+}
+
+impl Visit for JsonVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .insert(field.name().to_string(), Value::from(value));
+    }
+}
+
+/// picks the session id out of a spans fields, if it has one
+#[derive(Default)]
+struct SessionVisitor {
+    session_id: Option<u64>,
+}
+
+impl Visit for SessionVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == SESSION_FIELD {
+            self.session_id = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
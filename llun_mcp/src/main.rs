@@ -1,31 +1,98 @@
-use llun_mcp::LlunServer;
+use clap::{Parser, ValueEnum};
+use llun_mcp::{LlunServer, http, logging::McpLogLayer, workspace::Workspace};
 use rmcp::{transport::stdio, ServiceExt};
-use tracing_subscriber::{self, EnvFilter};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing_subscriber::{self, EnvFilter, Layer, layer::SubscriberExt, util::SubscriberInitExt};
 use tracing::{info, error};
 
+/// how the server talks to its clients
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum Transport {
+    /// a single client over stdin/stdout, launched by the client itself
+    #[default]
+    Stdio,
+    /// any number of clients over streamable http (and legacy sse), i.e. one shared server per dev container
+    Http,
+}
+
+/// MCP server exposing llun's rules and checks to coding agents
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// transport to serve over
+    #[arg(long, value_enum, default_value_t)]
+    transport: Transport,
+
+    /// address to listen on with '--transport http'
+    #[arg(long, default_value = "127.0.0.1:8000")]
+    bind: SocketAddr,
+
+    /// project root to read config, rules and files from. defaults to the clients root if it
+    /// shares one, or the working directory if not. over http clients roots are never followed
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// require clients to send 'Authorization: Bearer <token>' with '--transport http'
+    #[arg(long, env = "LLUN_MCP_AUTH_TOKEN", hide_env_values = true)]
+    auth_token: Option<String>,
+
+    /// allow '--transport http' to listen beyond loopback without an auth token, i.e. on a trusted network
+    #[arg(long)]
+    allow_unauthenticated: bool,
+}
+
 /// Run with: npx @modelcontextprotocol/inspector cargo run --bin llun-mcp
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into())
+    let args = Args::parse();
+
+    // stderr keeps its own filter, clients choose their level with 'logging/setLevel'
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(false)
+                .with_target(false)
+                .compact()
+                .with_filter(
+                    EnvFilter::from_default_env()
+                        .add_directive(tracing::Level::INFO.into())
+                ),
         )
-        .with_writer(std::io::stderr)
-        .with_ansi(false)
-        .with_target(false)
-        .compact()
+        .with(McpLogLayer)
         .init();
 
     info!("Starting llun MCP server");
+    let workspace = Workspace::new(args.root)?;
+
+    match args.transport {
+        Transport::Stdio => {
+            let service = LlunServer::new(Arc::new(workspace))
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    error!("Server error: {:?}", e);
+                })?;
 
-    let service = LlunServer::new()
-        .serve(stdio())
-        .await
-        .inspect_err(|e| {
-            error!("Server error: {:?}", e);
-        })?;
+            service.waiting().await?;
+        }
+        Transport::Http => {
+            info!("Serving {} to every client, their roots are ignored over http", workspace.root().display());
+            http::serve(
+                args.bind,
+                args.auth_token,
+                args.allow_unauthenticated,
+                Arc::new(workspace.ignore_client_roots()),
+            )
+            .await
+            .inspect_err(|e| match e.hint() {
+                Some(hint) => error!("{} ({})", e, hint),
+                None => error!("Server error: {:?}", e),
+            })?;
+        }
+    }
 
-    service.waiting().await?;    
     Ok(())
-}
\ No newline at end of file
+}
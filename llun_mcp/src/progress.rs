@@ -0,0 +1,134 @@
+use rmcp::{
+    Peer, RoleServer,
+    model::{ProgressNotificationParam, ProgressToken},
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use llun_core::api_client::ScanProgress;
+
+/// sends the client progress notifications for a single request, if it asked for them
+/// notifications are queued and sent in order by a background task, so reporting never blocks the scan
+pub struct Progress {
+    sender: Option<mpsc::UnboundedSender<ProgressNotificationParam>>,
+    token: Option<ProgressToken>,
+    sending: Option<JoinHandle<()>>,
+}
+
+impl Progress {
+    /// clients opt in by sending a progress token with the request
+    pub fn new(peer: Peer<RoleServer>, token: Option<ProgressToken>) -> Self {
+        let Some(token) = token else {
+            return Self::none();
+        };
+
+        let (sender, mut receiver) = mpsc::unbounded_channel::<ProgressNotificationParam>();
+        let sending = tokio::spawn(async move {
+            while let Some(notification) = receiver.recv().await {
+                if let Err(e) = peer.notify_progress(notification).await {
+                    debug!("Failed to send progress: {}", e);
+                    break;
+                }
+            }
+        });
+
+        Self {
+            sender: Some(sender),
+            token: Some(token),
+            sending: Some(sending),
+        }
+    }
+
+    /// for requests that didnt ask for progress
+    pub fn none() -> Self {
+        Self {
+            sender: None,
+            token: None,
+            sending: None,
+        }
+    }
+
+    /// wait for everything reported to be sent, the token is only valid until the request is answered
+    pub async fn finish(mut self) {
+        self.sender.take();
+        if let Some(sending) = self.sending.take() {
+            let _ = sending.await;
+        }
+    }
+
+    pub fn report(&self, progress: usize, total: usize, message: impl Into<String>) {
+        let (Some(sender), Some(token)) = (&self.sender, &self.token) else {
+            return;
+        };
+
+        // the receiver only goes away if the client has, so theres no one left to tell
+        let _ = sender.send(ProgressNotificationParam {
+            progress_token: token.clone(),
+            progress: progress as f64,
+            total: Some(total as f64),
+            message: Some(message.into()),
+        });
+    }
+}
+
+/// the steps of a check, in the order the client sees them:
+/// files loaded, then each batch sent and completed, for each group of files sharing a config
+pub struct CheckProgress {
+    progress: Progress,
+    /// groups are checked one after another, each counting its batches from 1
+    groups: usize,
+    /// a guess until the first batch is sent, batched checks only know once the cache has been read
+    total: AtomicUsize,
+    /// batches in production mode run at once, so count steps rather than trusting batch numbers
+    steps: AtomicUsize,
+}
+
+impl CheckProgress {
+    pub fn new(progress: Progress, batches: usize, groups: usize) -> Self {
+        let groups = groups.max(1);
+        Self {
+            progress,
+            groups,
+            total: AtomicUsize::new(Self::steps_for(batches) * groups),
+            steps: AtomicUsize::new(0),
+        }
+    }
+
+    pub async fn finish(self) {
+        self.progress.finish().await;
+    }
+
+    pub fn scan(&self, event: ScanProgress) {
+        match event {
+            ScanProgress::FilesLoaded { files } => self.step(format!("Loaded {} file(s)", files)),
+            ScanProgress::BatchSent { batch, total } => {
+                self.store_total(total);
+                self.step(format!("Sent batch {} of {}", batch, total))
+            }
+            ScanProgress::BatchCompleted { batch, total } => {
+                self.store_total(total);
+                self.step(format!("Completed batch {} of {}", batch, total))
+            }
+        }
+    }
+
+    /// a guess at every group needing as many batches as this one, never less than whats already been done
+    fn store_total(&self, batches: usize) {
+        let total = Self::steps_for(batches) * self.groups;
+        let steps = self.steps.load(Ordering::SeqCst) + 1;
+        self.total.store(total.max(steps), Ordering::SeqCst);
+    }
+
+    fn step(&self, message: String) {
+        let step = self.steps.fetch_add(1, Ordering::SeqCst) + 1;
+        self.progress
+            .report(step, self.total.load(Ordering::SeqCst), message);
+    }
+
+    /// loading the files, then sending and completing each batch
+    fn steps_for(batches: usize) -> usize {
+        1 + batches * 2
+    }
+}
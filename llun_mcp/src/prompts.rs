@@ -0,0 +1,73 @@
+use rmcp::{
+    handler::server::wrapper::Parameters,
+    model::{PromptMessage, PromptMessageRole},
+    prompt, prompt_router,
+};
+use rmcp::model::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+
+use llun_core::api_client::PromptManager;
+
+use crate::LlunServer;
+use crate::config::internal_error;
+
+/// what the agent is being asked to review
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ReviewPromptArgs {
+    /// the files, change or code to review against the rules
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+/// what the agent is being asked to design
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DesignPromptArgs {
+    /// the feature or change to design
+    #[serde(default)]
+    pub task: Option<String>,
+}
+
+/// dispatch all 'prompt's to our mcp server
+#[prompt_router(vis = "pub(crate)")]
+impl LlunServer {
+    /// MCP 'prompt' asking the agent to review work against the selected rules
+    #[prompt(
+        name = "review-with-llun",
+        description = "Review code against the users selected architectural rules, citing rule codes for each violation"
+    )]
+    async fn review_with_llun(
+        &self,
+        Parameters(args): Parameters<ReviewPromptArgs>,
+    ) -> Result<Vec<PromptMessage>, McpError> {
+        let task = args
+            .target
+            .unwrap_or_else(|| "The changes made in this session.".to_string());
+        self.rules_prompt("agent_prompt_review.txt", &task)
+    }
+
+    /// MCP 'prompt' asking the agent to design something that follows the selected rules
+    #[prompt(
+        name = "design-with-llun",
+        description = "Design a solution that follows the users selected architectural rules from the start"
+    )]
+    async fn design_with_llun(
+        &self,
+        Parameters(args): Parameters<DesignPromptArgs>,
+    ) -> Result<Vec<PromptMessage>, McpError> {
+        let task = args
+            .task
+            .unwrap_or_else(|| "The task the user is about to describe.".to_string());
+        self.rules_prompt("agent_prompt_design.txt", &task)
+    }
+}
+
+impl LlunServer {
+    /// a single user message with the selected rules embedded
+    fn rules_prompt(&self, prompt_filename: &str, task: &str) -> Result<Vec<PromptMessage>, McpError> {
+        let rules = self.selected_rules()?;
+        let prompt = PromptManager::load_agent_prompt(prompt_filename, &rules, task)
+            .map_err(internal_error("Failed to build prompt"))?;
+
+        Ok(vec![PromptMessage::new_text(PromptMessageRole::User, prompt)])
+    }
+}
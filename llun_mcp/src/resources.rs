@@ -0,0 +1,144 @@
+use rmcp::model::{
+    AnnotateAble, RawResource, RawResourceTemplate, ReadResourceResult, Resource, ResourceContents,
+    ResourceTemplate,
+};
+use rmcp::model::ErrorData as McpError;
+use serde_json::{Value, json};
+
+use llun_core::rules::{Rule, RuleManager, RuleSet};
+
+use crate::config::internal_error;
+
+/// every selected rule, listed with where to read it
+pub const RULES_INDEX_URI: &str = "llun://rules";
+const RULE_URI_PREFIX: &str = "llun://rules/";
+const MARKDOWN: &str = "text/markdown";
+const JSON: &str = "application/json";
+
+pub fn rule_uri(rule_code: &str) -> String {
+    format!("{}{}", RULE_URI_PREFIX, rule_code)
+}
+
+/// the rule as its json file, plus the code the file name gives it
+pub fn rule_json(rule: &Rule) -> Value {
+    let mut value = serde_json::to_value(rule).unwrap_or_else(|_| json!({}));
+    value["rule_code"] = Value::from(rule.rule_code.clone());
+    value
+}
+
+/// the index, followed by a resource per selected rule
+pub fn list_rule_resources(rules: &RuleSet) -> Vec<Resource> {
+    let index = RawResource {
+        description: Some("Index of the architectural rules selected for this workspace".to_string()),
+        mime_type: Some(JSON.to_string()),
+        ..RawResource::new(RULES_INDEX_URI, "rules")
+    };
+
+    std::iter::once(index.no_annotation())
+        .chain(rules.iter().map(|rule| {
+            RawResource {
+                title: Some(format!("{} - {}", rule.rule_code, rule.name)),
+                description: Some(rule.description.clone()),
+                mime_type: Some(MARKDOWN.to_string()),
+                ..RawResource::new(rule_uri(&rule.rule_code), rule.rule_code.clone())
+            }
+            .no_annotation()
+        }))
+        .collect()
+}
+
+/// any rule can be read by code, selected or not
+pub fn rule_templates() -> Vec<ResourceTemplate> {
+    vec![
+        RawResourceTemplate {
+            uri_template: format!("{}{{code}}", RULE_URI_PREFIX),
+            name: "rule".to_string(),
+            title: Some("Llun rule".to_string()),
+            description: Some(
+                "A single architectural rule by code i.e. 'SOLID01', as Markdown and JSON".to_string(),
+            ),
+            mime_type: Some(MARKDOWN.to_string()),
+        }
+        .no_annotation(),
+    ]
+}
+
+/// the index, or a single rule rendered as both markdown and json
+pub fn read_rule_resource(
+    uri: &str,
+    selected: &RuleSet,
+    rule_manager: &RuleManager,
+) -> Result<ReadResourceResult, McpError> {
+    if uri == RULES_INDEX_URI {
+        let index = serde_json::to_string_pretty(&rules_index(selected))
+            .map_err(internal_error("Failed to serialise rules index"))?;
+        return Ok(ReadResourceResult {
+            contents: vec![text_contents(uri, JSON, index)],
+        });
+    }
+
+    let rule_code = uri
+        .strip_prefix(RULE_URI_PREFIX)
+        .filter(|code| !code.is_empty())
+        .ok_or_else(|| resource_not_found(uri))?;
+
+    let rule = match selected.iter().find(|rule| rule.rule_code == rule_code) {
+        Some(rule) => rule.clone(),
+        None => load_single_rule(rule_code, rule_manager).ok_or_else(|| resource_not_found(uri))?,
+    };
+
+    let rule_json = serde_json::to_string_pretty(&rule_json(&rule))
+        .map_err(internal_error("Failed to serialise rule"))?;
+
+    Ok(ReadResourceResult {
+        contents: vec![
+            text_contents(uri, MARKDOWN, rule.to_string()),
+            text_contents(uri, JSON, rule_json),
+        ],
+    })
+}
+
+fn rules_index(selected: &RuleSet) -> Value {
+    let rules: Vec<Value> = selected
+        .iter()
+        .map(|rule| {
+            json!({
+                "rule_code": rule.rule_code,
+                "name": rule.name,
+                "severity": rule.severity,
+                "uri": rule_uri(&rule.rule_code),
+            })
+        })
+        .collect();
+
+    json!({ "rules": rules })
+}
+
+/// only exact rule codes, a family would otherwise expand into several rules
+pub(crate) fn load_single_rule(rule_code: &str, rule_manager: &RuleManager) -> Option<Rule> {
+    if !rule_manager.valid_rules().contains(rule_code) {
+        return None;
+    }
+
+    rule_manager
+        .load_from_cli(vec![rule_code.to_string()], Vec::new(), Vec::new())
+        .ok()?
+        .into_iter()
+        .next()
+}
+
+fn text_contents(uri: &str, mime_type: &str, text: String) -> ResourceContents {
+    ResourceContents::TextResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some(mime_type.to_string()),
+        text,
+        meta: None,
+    }
+}
+
+fn resource_not_found(uri: &str) -> McpError {
+    McpError::resource_not_found(
+        format!("No llun rule at '{}', see {} for the selected rules", uri, RULES_INDEX_URI),
+        None,
+    )
+}
@@ -0,0 +1,183 @@
+use rmcp::model::ErrorData as McpError;
+use serde::{Deserialize, Serialize};
+
+use llun_core::rules::{Rule, RuleExample, RuleManager, RuleSet, RuleSource, Severity};
+
+use crate::config::internal_error;
+use crate::resources::{load_single_rule, rule_uri};
+use crate::workspace::Workspace;
+
+/// which rules the agent wants listed, all of them by default
+#[derive(Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListRulesArgs {
+    /// only list rules in this family i.e. 'DOMAIN'
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub family: Option<String>,
+
+    /// only list the rules selected by the users config
+    #[serde(default)]
+    pub selected_only: bool,
+}
+
+/// the rule the agent wants explained
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExplainRuleArgs {
+    /// code of the rule to explain i.e. 'DOMAIN02'
+    pub rule_code: String,
+}
+
+/// enough about a rule to decide whether to look at it properly
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RuleSummary {
+    pub rule_code: String,
+    pub name: String,
+    pub family: String,
+    pub severity: Severity,
+    pub source: RuleSource,
+    /// whether the users config selects this rule
+    pub selected: bool,
+    /// where to read the rule as an mcp resource
+    pub uri: String,
+}
+
+/// every listed rule, ordered by code
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RuleList {
+    pub rules: Vec<RuleSummary>,
+}
+
+/// a rule in full, for citing in design decisions
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RuleDetails {
+    pub rule_code: String,
+    pub name: String,
+    pub family: String,
+    pub description: String,
+    pub risk_if_violated: String,
+    pub examples: Vec<RuleExample>,
+    pub severity: Severity,
+    pub source: RuleSource,
+    /// whether the users config selects this rule
+    pub selected: bool,
+}
+
+/// every available rule, narrowed by family and selection if asked
+pub fn list_rules(
+    args: &ListRulesArgs,
+    selected: &RuleSet,
+    workspace: &Workspace,
+) -> Result<RuleList, McpError> {
+    let rule_manager = workspace.rule_manager().map_err(internal_error("Failed to initialize rules"))?;
+    let all_rules = rule_manager
+        .load_all()
+        .map_err(internal_error("Failed to load rules"))?;
+
+    let rules = all_rules
+        .iter()
+        .filter(|rule| {
+            args.family
+                .as_ref()
+                .is_none_or(|family| rule.family().eq_ignore_ascii_case(family))
+        })
+        .map(|rule| RuleSummary {
+            rule_code: rule.rule_code.clone(),
+            name: rule.name.clone(),
+            family: rule.family().to_string(),
+            severity: rule.severity,
+            source: RuleManager::rule_source(&rule.rule_code),
+            selected: is_selected(rule, selected),
+            uri: rule_uri(&rule.rule_code),
+        })
+        .filter(|summary| summary.selected || !args.selected_only)
+        .collect();
+
+    Ok(RuleList { rules })
+}
+
+/// a single rule by its exact code, selected or not
+pub fn explain_rule(
+    args: &ExplainRuleArgs,
+    selected: &RuleSet,
+    workspace: &Workspace,
+) -> Result<RuleDetails, McpError> {
+    let rule_code = args.rule_code.trim();
+    let rule_manager = workspace.rule_manager().map_err(internal_error("Failed to initialize rules"))?;
+    // user rules are loaded by their exact file name, but built-in codes are all uppercase
+    // so an agent that lowercased one can still find it
+    let rule = load_single_rule(rule_code, &rule_manager)
+        .or_else(|| {
+            let built_in_code = rule_code.to_uppercase();
+            (RuleManager::rule_source(&built_in_code) == RuleSource::BuiltIn)
+                .then(|| load_single_rule(&built_in_code, &rule_manager))
+                .flatten()
+        })
+        .ok_or_else(|| {
+            McpError::invalid_params(
+                format!("Unknown rule '{}', call 'list_rules' to see the available codes", rule_code),
+                None,
+            )
+        })?;
+
+    Ok(RuleDetails {
+        family: rule.family().to_string(),
+        source: RuleManager::rule_source(&rule.rule_code),
+        selected: is_selected(&rule, selected),
+        rule_code: rule.rule_code,
+        name: rule.name,
+        description: rule.description,
+        risk_if_violated: rule.risk_if_violated,
+        examples: rule.examples,
+        severity: rule.severity,
+    })
+}
+
+/// a plain text overview for clients that dont read structured content
+pub fn summarise_list(list: &RuleList) -> String {
+    if list.rules.is_empty() {
+        return "No rules matched.".to_string();
+    }
+
+    list.rules
+        .iter()
+        .map(|rule| {
+            format!(
+                "- {} ({}) {} [{}, {}{}]",
+                rule.rule_code,
+                rule.family,
+                rule.name,
+                rule.severity,
+                rule.source,
+                if rule.selected { ", selected" } else { "" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// the rule as markdown, the same as the rules section of a review prompt
+pub fn summarise_details(details: &RuleDetails) -> String {
+    let mut summary = format!(
+        "## {} - {}\n*{}*\n**Risk if violated:** {}\n**Severity:** {}\n**Source:** {}\n**Selected:** {}\n",
+        details.rule_code,
+        details.name,
+        details.description,
+        details.risk_if_violated,
+        details.severity,
+        details.source,
+        if details.selected { "yes" } else { "no" }
+    );
+    for example in &details.examples {
+        summary.push_str(&format!(
+            "- Violation: {}\n  Better: {}\n",
+            example.violation, example.better
+        ));
+    }
+
+    summary
+}
+
+fn is_selected(rule: &Rule, selected: &RuleSet) -> bool {
+    selected
+        .iter()
+        .any(|selected_rule| selected_rule.rule_code == rule.rule_code)
+}
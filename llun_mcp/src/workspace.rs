@@ -0,0 +1,253 @@
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher, recommended_watcher};
+use rmcp::model::Root;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::{debug, info, warn};
+
+use llun_core::rules::{RuleManager, RuleManagerError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum WorkspaceError {
+    #[error("Workspace root {0} is not a directory")]
+    NotADirectory(PathBuf),
+    #[error("Failed to use the workspace root {0}")]
+    RootError(PathBuf, #[source] std::io::Error),
+    #[error("Only 'file://' roots are supported, got '{0}'")]
+    UnsupportedRoot(String),
+    #[error("Path '{0}' couldnt be found in the workspace")]
+    PathNotFound(String, #[source] std::io::Error),
+    #[error("Path '{0}' is outside the workspace root")]
+    OutsideRoot(String),
+}
+
+/// contents of each config file we've read, or None if it doesnt exist, keyed by absolute path
+type ConfigFiles = Arc<Mutex<HashMap<PathBuf, Option<String>>>>;
+
+/// the directory llun treats as the project root, files, rules and configs are all read from
+/// under it rather than the working directory. over stdio it follows the one clients roots,
+/// over http every session shares it, so it stays put
+pub struct Workspace {
+    root: RwLock<PathBuf>,
+    /// set with '--root' or when shared between sessions, in which case the clients roots are ignored
+    pinned: bool,
+    config_files: ConfigFiles,
+    /// dropping the watcher stops the watch, so it lives as long as the root does
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    /// only the directories configs have been looked for in are watched, never the whole tree
+    watched: Mutex<HashSet<PathBuf>>,
+}
+
+impl Workspace {
+    /// use the given root, or the working directory if there isnt one
+    pub fn new(root: Option<PathBuf>) -> Result<Self, WorkspaceError> {
+        let pinned = root.is_some();
+        let root = match root {
+            Some(root) => root,
+            None => std::env::current_dir()
+                .map_err(|e| WorkspaceError::RootError(PathBuf::from("."), e))?,
+        };
+
+        let workspace = Self {
+            root: RwLock::new(PathBuf::new()),
+            pinned,
+            config_files: ConfigFiles::default(),
+            watcher: Mutex::new(None),
+            watched: Mutex::new(HashSet::new()),
+        };
+        workspace.use_root(&root)?;
+
+        Ok(workspace)
+    }
+
+    /// keep the root wherever it started, for when sessions share the workspace and one
+    /// clients roots would otherwise move every other clients checks
+    pub fn ignore_client_roots(mut self) -> Self {
+        self.pinned = true;
+        self
+    }
+
+    pub fn root(&self) -> PathBuf {
+        self.root.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// move to the first of the clients roots, unless the root was given on the command line
+    pub fn use_client_roots(&self, roots: &[Root]) -> Result<(), WorkspaceError> {
+        if self.pinned {
+            return Ok(());
+        }
+        let Some(root) = roots.first() else {
+            debug!("Client sent no roots, staying in {}", self.root().display());
+            return Ok(());
+        };
+
+        let root_path = url::Url::parse(&root.uri)
+            .ok()
+            .filter(|url| url.scheme() == "file")
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(|| WorkspaceError::UnsupportedRoot(root.uri.clone()))?;
+        if roots.len() > 1 {
+            info!("Client sent {} roots, using the first", roots.len());
+        }
+
+        self.use_root(&root_path)
+    }
+
+    /// the built-in rules, plus the users own from the 'llun' folder under the root
+    pub fn rule_manager(&self) -> Result<RuleManager, RuleManagerError> {
+        RuleManager::in_dir(&self.root())
+    }
+
+    /// a path from the client, checked to stay inside the root once links and '..'s are followed
+    /// given back relative to the root, so files are named the same as 'llun check' would name them
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, WorkspaceError> {
+        let root = self.root();
+        let resolved = root
+            .join(path)
+            .canonicalize()
+            .map_err(|e| WorkspaceError::PathNotFound(path.to_string(), e))?;
+        let relative = resolved
+            .strip_prefix(&root)
+            .map_err(|_| WorkspaceError::OutsideRoot(path.to_string()))?;
+
+        if relative.as_os_str().is_empty() {
+            return Ok(PathBuf::from("."));
+        }
+        Ok(relative.to_path_buf())
+    }
+
+    /// a file name from the client that might not be saved yet, i.e. the name of inline code
+    /// checked the same way as 'resolve' if it exists, otherwise any '..' or absolute path is refused outright
+    pub fn resolve_name(&self, name: &str) -> Result<PathBuf, WorkspaceError> {
+        let path = Path::new(name);
+        if path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(WorkspaceError::OutsideRoot(name.to_string()));
+        }
+
+        match self.resolve(name) {
+            Err(WorkspaceError::PathNotFound(..)) => Ok(path
+                .components()
+                .filter(|component| *component != Component::CurDir)
+                .collect()),
+            resolved => resolved,
+        }
+    }
+
+    /// contents of a config file relative to the root, read once then kept until it changes on disk
+    pub fn read_config(&self, path: &Path) -> Option<String> {
+        let root = self.root();
+        let path = root.join(path);
+        let watching = self.watcher.lock().unwrap_or_else(|e| e.into_inner()).is_some();
+        if !watching {
+            return Self::read_under(&root, &path);
+        }
+
+        if let Some(contents) = self.config_files.lock().unwrap_or_else(|e| e.into_inner()).get(&path) {
+            return contents.clone();
+        }
+        // watched before reading, so a change made straight after cant be missed
+        if let Some(directory) = path.parent() {
+            self.watch_directory(directory);
+        }
+        debug!("Reading {}", path.display());
+        let contents = Self::read_under(&root, &path);
+        self.config_files
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path, contents.clone());
+
+        contents
+    }
+
+    /// a files contents, as long as it really is under the root once links are followed
+    fn read_under(root: &Path, path: &Path) -> Option<String> {
+        let path = path.canonicalize().ok()?;
+        if !path.starts_with(root) {
+            warn!("Ignoring {}, it is outside the workspace root", path.display());
+            return None;
+        }
+
+        std::fs::read_to_string(path).ok()
+    }
+
+    fn use_root(&self, root: &Path) -> Result<(), WorkspaceError> {
+        if !root.is_dir() {
+            return Err(WorkspaceError::NotADirectory(root.to_path_buf()));
+        }
+        let root = root
+            .canonicalize()
+            .map_err(|e| WorkspaceError::RootError(root.to_path_buf(), e))?;
+        if root == self.root() {
+            return Ok(());
+        }
+
+        info!("Using workspace root {}", root.display());
+
+        *self.root.write().unwrap_or_else(|e| e.into_inner()) = root.clone();
+        self.config_files.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.watched.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        *self.watcher.lock().unwrap_or_else(|e| e.into_inner()) = self.watcher();
+        self.watch_directory(&root);
+
+        Ok(())
+    }
+
+    /// drop any cached config under a changed path, so the next call rereads it
+    /// without a watcher we'd never know the cache was stale, so we fall back to reading every time
+    /// no debouncing here, dropping an entry twice costs nothing
+    fn watcher(&self) -> Option<RecommendedWatcher> {
+        let config_files = Arc::clone(&self.config_files);
+        let watcher = recommended_watcher(move |result: notify::Result<Event>| {
+            // our own reads show up as accesses, which would empty the cache as fast as we fill it
+            let Ok(event) = result.inspect_err(|e| debug!("Config watch error: {}", e)) else {
+                return;
+            };
+            if event.kind.is_access() {
+                return;
+            }
+
+            let mut config_files = config_files.lock().unwrap_or_else(|e| e.into_inner());
+            // anything other than a config (or a directory holding one) changing is left alone
+            // a changed directory could have been a move or delete of everything below it
+            config_files.retain(|config_path, _| {
+                let changed = event.paths.iter().any(|path| config_path.starts_with(path));
+                if changed {
+                    info!("{} changed, reloading", config_path.display());
+                }
+                !changed
+            });
+        });
+
+        watcher
+            .inspect_err(|e| warn!("Failed to watch config files, they'll be reread on every call: {}", e))
+            .ok()
+    }
+
+    /// watch a single directory for its configs changing, without descending into it
+    /// a directory that doesnt exist yet cant hold a config, so failing to watch it is fine,
+    /// its parent is watched, so creating it drops the cached configs under it and it gets another go
+    fn watch_directory(&self, directory: &Path) {
+        let mut watched = self.watched.lock().unwrap_or_else(|e| e.into_inner());
+        if watched.contains(directory) {
+            return;
+        }
+
+        let mut watcher = self.watcher.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(watcher) = watcher.as_mut() {
+            match watcher.watch(directory, RecursiveMode::NonRecursive) {
+                Ok(()) => {
+                    debug!("Watching {} for config changes", directory.display());
+                    watched.insert(directory.to_path_buf());
+                }
+                Err(e) => debug!("Not watching {}: {}", directory.display(), e),
+            }
+        }
+    }
+}
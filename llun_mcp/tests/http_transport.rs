@@ -0,0 +1,347 @@
+use rmcp::{
+    ClientHandler, RoleClient, ServiceError, ServiceExt,
+    model::{
+        CallToolRequestParam, ClientInfo, ErrorCode, LoggingLevel, LoggingMessageNotificationParam,
+        SetLevelRequestParam,
+    },
+    service::NotificationContext,
+    transport::{
+        SseClientTransport, StreamableHttpClientTransport, sse_client::SseClientConfig,
+        streamable_http_client::StreamableHttpClientTransportConfig,
+    },
+};
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use llun_mcp::http::{MCP_PATH, SSE_PATH, router};
+use llun_mcp::logging::McpLogLayer;
+use llun_mcp::workspace::Workspace;
+
+const TOKEN: &str = "test-token";
+
+/// serve both transports on a free local port, for as long as the test runs
+async fn start_server(auth_token: Option<&str>) -> (SocketAddr, CancellationToken) {
+    start_server_in(auth_token, None).await
+}
+
+async fn start_server_in(auth_token: Option<&str>, root: Option<PathBuf>) -> (SocketAddr, CancellationToken) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind = listener.local_addr().unwrap();
+    let workspace = Arc::new(Workspace::new(root).unwrap());
+    let (router, ct) = router(bind, auth_token.map(str::to_string), workspace);
+
+    let shutdown = ct.clone();
+    tokio::spawn(async move {
+        axum::serve(listener, router)
+            .with_graceful_shutdown(async move { shutdown.cancelled().await })
+            .await
+            .unwrap();
+    });
+
+    (bind, ct)
+}
+
+/// an openai compatible provider that finds one SOLID01 issue, in whichever file it was shown first
+async fn start_mock_provider() -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind = listener.local_addr().unwrap();
+    let router = axum::Router::new().route(
+        "/chat/completions",
+        axum::routing::post(|axum::Json(request): axum::Json<Value>| async move {
+            let prompt = request["messages"][1]["content"].as_str().unwrap_or_default();
+            let file_path = if prompt.contains("billing/b.py") { "billing/b.py" } else { "a.py" };
+            let content = json!({ "detected_issues": [{
+                "rule_code": "SOLID01",
+                "name": "Single Responsibility",
+                "file_path": file_path,
+                "brief_description": "Does too much",
+                "explanation": "Two reasons to change in one class.",
+                "suggested_alternative": "Split it up.",
+                "code_snippet": "class App:",
+            }]});
+            axum::Json(json!({
+                "id": "mock",
+                "object": "chat.completion",
+                "created": 0,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": content.to_string() },
+                    "finish_reason": "stop",
+                }],
+            }))
+        }),
+    );
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    bind
+}
+
+/// a client that sends the token on every request, the rmcp client's own 'auth_header'
+/// is only sent with posts so would be rejected when opening the event stream
+fn authorised_http_client() -> reqwest::Client {
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert(
+        reqwest::header::AUTHORIZATION,
+        format!("Bearer {}", TOKEN).parse().unwrap(),
+    );
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap()
+}
+
+/// a client that keeps every log message the server sends it
+#[derive(Clone, Default)]
+struct LogCollector {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+impl LogCollector {
+    fn received(&self, text: &str) -> bool {
+        self.messages.lock().unwrap().iter().any(|message| message.contains(text))
+    }
+}
+
+impl ClientHandler for LogCollector {
+    async fn on_logging_message(
+        &self,
+        params: LoggingMessageNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        self.messages.lock().unwrap().push(params.data.to_string());
+    }
+}
+
+fn tool_names(tools: &[rmcp::model::Tool]) -> Vec<String> {
+    tools.iter().map(|tool| tool.name.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_streamable_http_serves_tools_and_prompts() {
+    let (bind, ct) = start_server(None).await;
+
+    let transport = StreamableHttpClientTransport::from_uri(format!("http://{}{}", bind, MCP_PATH));
+    let client = ClientInfo::default().serve(transport).await.unwrap();
+
+    let tools = client.list_all_tools().await.unwrap();
+    let names = tool_names(&tools);
+    assert!(names.contains(&"get_rules".to_string()));
+    assert!(names.contains(&"check_code".to_string()));
+
+    let prompts = client.list_all_prompts().await.unwrap();
+    assert!(prompts.iter().any(|prompt| prompt.name == "review-with-llun"));
+
+    client.cancel().await.unwrap();
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn test_streamable_http_accepts_bearer_token() {
+    let (bind, ct) = start_server(Some(TOKEN)).await;
+
+    let transport = StreamableHttpClientTransport::with_client(
+        authorised_http_client(),
+        StreamableHttpClientTransportConfig::with_uri(format!("http://{}{}", bind, MCP_PATH)),
+    );
+    let client = ClientInfo::default().serve(transport).await.unwrap();
+
+    let tools = client.list_all_tools().await.unwrap();
+    assert!(tool_names(&tools).contains(&"get_rules".to_string()));
+
+    client.cancel().await.unwrap();
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn test_streamable_http_rejects_missing_or_wrong_token() {
+    let (bind, ct) = start_server(Some(TOKEN)).await;
+    let url = format!("http://{}{}", bind, MCP_PATH);
+
+    let transport = StreamableHttpClientTransport::from_uri(url.clone());
+    assert!(ClientInfo::default().serve(transport).await.is_err());
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .bearer_auth("wrong-token")
+        .header("Accept", "application/json, text/event-stream")
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "ping"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    assert_eq!(response.headers()["www-authenticate"], "Bearer");
+
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn test_legacy_sse_serves_tools_with_bearer_token() {
+    let (bind, ct) = start_server(Some(TOKEN)).await;
+
+    let transport = SseClientTransport::start_with_client(
+        authorised_http_client(),
+        SseClientConfig {
+            sse_endpoint: format!("http://{}{}", bind, SSE_PATH).into(),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let client = ClientInfo::default().serve(transport).await.unwrap();
+
+    let tools = client.list_all_tools().await.unwrap();
+    assert!(tool_names(&tools).contains(&"check_code".to_string()));
+
+    client.cancel().await.unwrap();
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn test_check_code_rejects_paths_outside_the_root() {
+    let (bind, ct) = start_server(None).await;
+
+    let transport = StreamableHttpClientTransport::from_uri(format!("http://{}{}", bind, MCP_PATH));
+    let client = ClientInfo::default().serve(transport).await.unwrap();
+
+    let outside = std::env::temp_dir();
+    for path in ["../", outside.to_str().unwrap()] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "check_code".into(),
+                arguments: serde_json::json!({ "paths": [path] }).as_object().cloned(),
+            })
+            .await;
+
+        match result {
+            Err(ServiceError::McpError(error)) => {
+                assert_eq!(error.code, ErrorCode::INVALID_PARAMS, "{}", path);
+                assert!(error.message.contains("outside the workspace root"), "{}", error.message);
+            }
+            other => panic!("expected '{}' to be rejected, got {:?}", path, other),
+        }
+    }
+
+    client.cancel().await.unwrap();
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn test_get_rules_rejects_file_paths_outside_the_root() {
+    let (bind, ct) = start_server(None).await;
+
+    let transport = StreamableHttpClientTransport::from_uri(format!("http://{}{}", bind, MCP_PATH));
+    let client = ClientInfo::default().serve(transport).await.unwrap();
+
+    let outside = std::env::temp_dir().join("outside.py");
+    for file_path in ["../outside.py", "src/../../outside.py", outside.to_str().unwrap()] {
+        let result = client
+            .call_tool(CallToolRequestParam {
+                name: "get_rules".into(),
+                arguments: serde_json::json!({ "file_path": file_path }).as_object().cloned(),
+            })
+            .await;
+
+        match result {
+            Err(ServiceError::McpError(error)) => {
+                assert_eq!(error.code, ErrorCode::INVALID_PARAMS, "{}", file_path);
+                assert!(error.message.contains("outside the workspace root"), "{}", error.message);
+            }
+            other => panic!("expected '{}' to be rejected, got {:?}", file_path, other),
+        }
+    }
+
+    client.cancel().await.unwrap();
+    ct.cancel();
+}
+
+#[tokio::test]
+async fn test_check_code_uses_nested_configs() {
+    let provider = start_mock_provider().await;
+    let root = std::env::temp_dir().join(format!("llun-mcp-nested-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("billing")).unwrap();
+    std::fs::write(
+        root.join("llun.toml"),
+        format!(
+            "provider = \"openai\"\nselect = [\"SOLID\"]\n[scanners.openai]\napi_base = \"http://{}\"\n[severity]\nSOLID01 = \"info\"",
+            provider
+        ),
+    )
+    .unwrap();
+    std::fs::write(root.join("billing/llun.toml"), "[severity]\nSOLID01 = \"error\"").unwrap();
+    std::fs::write(root.join("a.py"), "class App:\n    pass\n").unwrap();
+    std::fs::write(root.join("billing/b.py"), "class Billing:\n    pass\n").unwrap();
+
+    let (bind, ct) = start_server_in(None, Some(root.clone())).await;
+    let transport = StreamableHttpClientTransport::from_uri(format!("http://{}{}", bind, MCP_PATH));
+    let client = ClientInfo::default().serve(transport).await.unwrap();
+
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: "check_code".into(),
+            arguments: json!({ "paths": ["."] }).as_object().cloned(),
+        })
+        .await
+        .unwrap();
+    let issues = &result.structured_content.unwrap()["detected_issues"];
+    let severity_of = |file_path: &str| {
+        issues
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|issue| issue["file_path"] == file_path)
+            .map(|issue| issue["severity"].clone())
+    };
+
+    assert_eq!(severity_of("a.py"), Some(json!("info")));
+    assert_eq!(severity_of("billing/b.py"), Some(json!("error")));
+
+    client.cancel().await.unwrap();
+    ct.cancel();
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn test_logs_only_go_to_the_session_that_caused_them() {
+    let _subscriber = tracing_subscriber::registry().with(McpLogLayer).set_default();
+    let (bind, ct) = start_server(None).await;
+
+    let mut clients = Vec::new();
+    for _ in 0..2 {
+        let logs = LogCollector::default();
+        let transport = StreamableHttpClientTransport::from_uri(format!("http://{}{}", bind, MCP_PATH));
+        let client = logs.clone().serve(transport).await.unwrap();
+        client
+            .set_level(SetLevelRequestParam {
+                level: LoggingLevel::Debug,
+            })
+            .await
+            .unwrap();
+        clients.push((client, logs));
+    }
+
+    let (caller, caller_logs) = &clients[0];
+    caller
+        .call_tool(CallToolRequestParam {
+            name: "get_rules".into(),
+            arguments: None,
+        })
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let (_, other_logs) = &clients[1];
+    assert!(caller_logs.received("Loaded rules"));
+    assert!(!other_logs.received("Loaded rules"));
+
+    for (client, _) in clients {
+        client.cancel().await.unwrap();
+    }
+    ct.cancel();
+}
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use llun_mcp::workspace::Workspace;
+
+/// a fresh directory for a single test to use as its workspace root
+fn temp_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("llun-mcp-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    root
+}
+
+/// the watcher runs on its own thread, so give it a moment to catch up
+fn eventually_reads(workspace: &Workspace, path: &Path, expected: Option<&str>) {
+    let started = Instant::now();
+    while workspace.read_config(path).as_deref() != expected {
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "{} never read as {:?}",
+            path.display(),
+            expected
+        );
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn test_configs_are_reread_after_they_change() {
+    let root = temp_root("reread");
+    std::fs::write(root.join("llun.toml"), "model = \"first\"").unwrap();
+    let workspace = Workspace::new(Some(root.clone())).unwrap();
+
+    eventually_reads(&workspace, Path::new("llun.toml"), Some("model = \"first\""));
+    std::fs::write(root.join("llun.toml"), "model = \"second\"").unwrap();
+    eventually_reads(&workspace, Path::new("llun.toml"), Some("model = \"second\""));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_nested_configs_are_picked_up_once_created() {
+    let root = temp_root("nested");
+    let workspace = Workspace::new(Some(root.clone())).unwrap();
+    let nested = Path::new("services/billing/llun.toml");
+
+    eventually_reads(&workspace, nested, None);
+    std::fs::create_dir_all(root.join("services/billing")).unwrap();
+    std::fs::write(root.join(nested), "select = [\"DOMAIN\"]").unwrap();
+    eventually_reads(&workspace, nested, Some("select = [\"DOMAIN\"]"));
+
+    std::fs::write(root.join(nested), "select = [\"SOLID\"]").unwrap();
+    eventually_reads(&workspace, nested, Some("select = [\"SOLID\"]"));
+
+    std::fs::remove_dir_all(root).unwrap();
+}